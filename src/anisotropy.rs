@@ -0,0 +1,105 @@
+use crate::error::DepletionError;
+
+/// Horizontal anisotropy of aquifer transmissivity relative to a straight stream.
+///
+/// The transmissivity passed to a model is the major principal transmissivity. For a straight
+/// stream, or boundaries parallel to it, only the transmissivity perpendicular to the stream
+/// controls depletion. The anisotropic aquifer is transformed into an equivalent isotropic one by
+/// stretching distances measured perpendicular to the stream.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Anisotropy {
+    /// Ratio of the minor to the major principal transmissivity (dimensionless).
+    pub ratio: f64,
+    /// Angle from the stream to the major principal direction (in radians).
+    pub principal_direction: f64,
+}
+
+impl Anisotropy {
+    pub fn new(ratio: f64, principal_direction: f64) -> Self {
+        Anisotropy {
+            ratio,
+            principal_direction,
+        }
+    }
+
+    /// Checks that the ratio is in the range (0, 1] and the direction is finite.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DepletionError::NonPhysicalParameter`] for the first parameter out of range.
+    pub fn validate(&self) -> Result<(), DepletionError> {
+        if !(self.ratio > 0.0 && self.ratio <= 1.0) {
+            return Err(DepletionError::NonPhysicalParameter {
+                name: "anisotropy ratio",
+                value: self.ratio,
+            });
+        }
+        if !self.principal_direction.is_finite() {
+            return Err(DepletionError::NonPhysicalParameter {
+                name: "principal direction",
+                value: self.principal_direction,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns true if the transmissivity is the same in every direction.
+    pub fn is_isotropic(&self) -> bool {
+        self.ratio == 1.0
+    }
+
+    /// Returns the ratio of the transmissivity perpendicular to the stream to the major principal transmissivity.
+    ///
+    /// With the major axis at angle `θ` from the stream this is `sin² θ + ratio * cos² θ`.
+    pub fn normal_transmissivity_ratio(&self) -> f64 {
+        if self.is_isotropic() {
+            return 1.0;
+        }
+        let (sin, cos) = self.principal_direction.sin_cos();
+        sin * sin + self.ratio * cos * cos
+    }
+
+    /// Transforms a distance perpendicular to the stream into the equivalent isotropic aquifer.
+    ///
+    /// # Parameters
+    ///
+    /// * `distance`: A distance measured perpendicular to the stream (in length units, typically feet).
+    ///
+    /// # Returns
+    ///
+    /// The distance in the equivalent isotropic aquifer with the major principal transmissivity.
+    /// For an isotropic aquifer the distance is returned unchanged.
+    pub fn transform_distance(&self, distance: f64) -> f64 {
+        if self.is_isotropic() {
+            return distance;
+        }
+        distance / self.normal_transmissivity_ratio().sqrt()
+    }
+}
+
+impl Default for Anisotropy {
+    fn default() -> Self {
+        Anisotropy::new(1.0, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_transform_distance() {
+        assert_eq!(Anisotropy::default().transform_distance(4000.0), 4000.0);
+        assert_eq!(Anisotropy::new(1.0, 0.3).transform_distance(4000.0), 4000.0);
+
+        // Major axis along the stream leaves the minor transmissivity across it
+        let along = Anisotropy::new(0.25, 0.0);
+        assert!((along.normal_transmissivity_ratio() - 0.25).abs() < 1e-12);
+        assert!((along.transform_distance(4000.0) - 8000.0).abs() < 1e-9);
+
+        // Major axis across the stream is the same as the isotropic aquifer
+        let across = Anisotropy::new(0.25, FRAC_PI_2);
+        assert!((across.transform_distance(4000.0) - 4000.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,1393 @@
+use crate::error::{DepletionError, check_positive};
+use crate::fft::convolve;
+use crate::model::DepletionModel;
+use crate::series::{DailySeries, MonthlySeries, PeriodSeries};
+use crate::units::VolumeUnit;
+use crate::utils::add_months;
+use chrono::{Days, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub type DepletionByStream = HashMap<i32, Vec<(NaiveDate, f64)>>;
+
+/// The method used to superimpose a model's response on the pumping history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ConvolutionMethod {
+    /// Spreads each month of pumping into daily rates and superimposes every day of pumping on
+    /// the daily unit response.
+    #[default]
+    DailySuperposition,
+    /// The same daily superposition evaluated as a convolution of dense daily arrays with the fast
+    /// Fourier transform, which is much faster for long pumping records.
+    ///
+    /// Only the daily arrays are convolved this way. Calendar months and stress periods have
+    /// unequal lengths, so the response to a month of pumping is not a shifted copy of the
+    /// response to another month, and [`ConvolutionMethod::MonthlyIntegrated`] sums directly.
+    FftSuperposition,
+    /// Integrates the response to each month of pumping over each result month using the
+    /// model's [`DepletionModel::depletion_volume`], which only needs one evaluation per day of
+    /// the horizon and one step per pair of months.
+    ///
+    /// The daily methods take the depletion fraction at the start of each day, so they lag the
+    /// exact integral by about half a day of pumping times the depletion fraction. This method
+    /// integrates exactly and is slightly higher while depletion is rising quickly, such as in
+    /// the first month of pumping. With [`Horizon::AverageMonths`] the response to pumping is
+    /// followed for the same number of days as in the daily methods.
+    MonthlyIntegrated,
+}
+
+/// How the length of the simulation horizon is determined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Horizon {
+    /// Results for `total_months` months, with the response to each day of pumping followed for
+    /// `total_months * days_per_month` days. Since pumping is spread over the real days of each
+    /// month, the last months can be truncated or over-run; this reproduces earlier reports.
+    #[default]
+    AverageMonths,
+    /// Results for `total_months` calendar months, with the response followed to the end of the
+    /// last month.
+    CalendarMonths,
+    /// Results for every month up to and including the month of the date, with the response
+    /// followed to the end of that month.
+    EndDate(NaiveDate),
+}
+
+/// How negative depletion values are reported.
+///
+/// Depletion can become negative at the end of a long horizon or through floating-point noise
+/// in the tail of the response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NegativeHandling {
+    /// Stops the results at the first negative value, treating it as complete aquifer depletion.
+    #[default]
+    Stop,
+    /// Reports negative values as zero.
+    Clamp,
+    /// Reports negative values unchanged.
+    Keep,
+}
+
+/// Options controlling which depletion values are reported in the `Vec` results.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReportingOptions {
+    /// Values with a magnitude at or below this are not reported.
+    pub threshold: f64,
+    /// Whether values that are not reported are given as zero, so every period is included.
+    pub zero_fill: bool,
+    /// How negative values are reported.
+    pub negative_handling: NegativeHandling,
+}
+
+impl Default for ReportingOptions {
+    /// Leaves out periods with depletion of 0.001 or less and stops at the first negative value.
+    fn default() -> Self {
+        ReportingOptions {
+            threshold: 0.001,
+            zero_fill: false,
+            negative_handling: NegativeHandling::Stop,
+        }
+    }
+}
+
+impl ReportingOptions {
+    /// Creates options that report every period unchanged, including zero and negative values.
+    pub fn dense() -> Self {
+        ReportingOptions {
+            threshold: 0.0,
+            zero_fill: true,
+            negative_handling: NegativeHandling::Keep,
+        }
+    }
+
+    /// Sets the magnitude at or below which values are not reported.
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets whether values that are not reported are given as zero.
+    pub fn with_zero_fill(mut self, zero_fill: bool) -> Self {
+        self.zero_fill = zero_fill;
+        self
+    }
+
+    /// Sets how negative values are reported.
+    pub fn with_negative_handling(mut self, negative_handling: NegativeHandling) -> Self {
+        self.negative_handling = negative_handling;
+        self
+    }
+}
+
+/// Options controlling how the engine calculates depletion.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DepletionOptions {
+    /// The average number of days per month used with [`Horizon::AverageMonths`].
+    pub days_per_month: f64,
+    /// The total number of months to calculate depletion for, unless the horizon is [`Horizon::EndDate`].
+    pub total_months: usize,
+    /// How the length of the horizon is determined.
+    pub horizon: Horizon,
+    /// The method used to superimpose the model's response on the pumping history.
+    pub method: ConvolutionMethod,
+    /// The unit of the pumping volumes.
+    pub pumping_unit: VolumeUnit,
+    /// The unit of the depletion volumes in the results.
+    pub output_unit: VolumeUnit,
+    /// Which depletion values are reported in the `Vec` results.
+    pub reporting: ReportingOptions,
+}
+
+impl DepletionOptions {
+    pub fn new(days_per_month: f64, total_months: usize) -> Self {
+        DepletionOptions {
+            days_per_month,
+            total_months,
+            horizon: Horizon::default(),
+            method: ConvolutionMethod::default(),
+            pumping_unit: VolumeUnit::default(),
+            output_unit: VolumeUnit::default(),
+            reporting: ReportingOptions::default(),
+        }
+    }
+
+    /// Creates options for a horizon of `total_months` calendar months.
+    pub fn calendar_months(total_months: usize) -> Self {
+        DepletionOptions::new(0.0, total_months).with_horizon(Horizon::CalendarMonths)
+    }
+
+    /// Creates options for a horizon ending with the month of `end_date`.
+    pub fn until(end_date: NaiveDate) -> Self {
+        DepletionOptions::new(0.0, 0).with_horizon(Horizon::EndDate(end_date))
+    }
+
+    /// Creates options with pumping and results in m³.
+    pub fn metric(days_per_month: f64, total_months: usize) -> Self {
+        DepletionOptions::new(days_per_month, total_months)
+            .with_pumping_unit(VolumeUnit::CubicMeters)
+            .with_output_unit(VolumeUnit::CubicMeters)
+    }
+
+    /// Sets the method used to superimpose the model's response on the pumping history.
+    pub fn with_method(mut self, method: ConvolutionMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Sets how the length of the horizon is determined.
+    pub fn with_horizon(mut self, horizon: Horizon) -> Self {
+        self.horizon = horizon;
+        self
+    }
+
+    /// Sets the unit of the pumping volumes, which is acre-ft by default.
+    pub fn with_pumping_unit(mut self, pumping_unit: VolumeUnit) -> Self {
+        self.pumping_unit = pumping_unit;
+        self
+    }
+
+    /// Sets the unit of the depletion volumes in the results, which is acre-ft by default.
+    pub fn with_output_unit(mut self, output_unit: VolumeUnit) -> Self {
+        self.output_unit = output_unit;
+        self
+    }
+
+    /// Sets which depletion values are reported in the `Vec` results.
+    pub fn with_reporting(mut self, reporting: ReportingOptions) -> Self {
+        self.reporting = reporting;
+        self
+    }
+}
+
+/// Calculates monthly streamflow depletion for any [`DepletionModel`].
+///
+/// This is the shared convolution engine behind every analytical method in the crate. The
+/// monthly pumping volumes are spread into daily rates, each day of pumping is superimposed
+/// on the model's unit response, and the resulting daily depletion is summed back into months.
+///
+/// # Parameters
+///
+/// * `model`: The depletion model supplying the depletion fraction over time.
+/// * `pumping_volumes_monthly`: A HashMap containing monthly pumping volumes in acre-ft/month, keyed by date.
+/// * `days_per_month`: The average number of days per month used in calculations.
+/// * `total_months`: The total number of months to calculate depletion for.
+///
+/// # Returns
+///
+/// A Vec of tuples, where each tuple contains a date and the corresponding monthly streamflow depletion in acre-ft/month.
+///
+/// The vector only includes months when the depletion is greater than 0.001 acre-ft/month.
+/// The calculation stops if a negative depletion value is encountered, indicating complete aquifer depletion.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] if there is no pumping, a parameter of the model or the
+/// horizon is not physically possible, or the horizon extends beyond the calendar.
+pub fn calculate_streamflow_depletion<M: DepletionModel + ?Sized>(
+    model: &M,
+    pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in acre-ft / month
+    days_per_month: f64,
+    total_months: usize,
+) -> Result<Vec<(NaiveDate, f64)>, DepletionError> {
+    calculate_streamflow_depletion_with_options(
+        model,
+        pumping_volumes_monthly,
+        &DepletionOptions::new(days_per_month, total_months),
+    )
+}
+
+/// Calculates monthly streamflow depletion for any [`DepletionModel`] with the given options.
+///
+/// With [`ConvolutionMethod::DailySuperposition`] this is the same as
+/// [`calculate_streamflow_depletion`], and [`ConvolutionMethod::FftSuperposition`] gives the
+/// same result to round-off. With [`ConvolutionMethod::MonthlyIntegrated`] the
+/// depletion of each month is calculated directly from the model's volume function, which
+/// matches the daily result closely and is much faster for long horizons. As in the daily
+/// method, depletion begins the day after the pumping occurs.
+///
+/// # Parameters
+///
+/// * `model`: The depletion model supplying the depletion fraction over time.
+/// * `pumping_volumes_monthly`: A HashMap containing monthly pumping volumes in `options.pumping_unit`, keyed by the first day of the month.
+/// * `options`: The horizon, units and method used for the calculation.
+///
+/// # Returns
+///
+/// A Vec of tuples, where each tuple contains a date and the corresponding monthly streamflow depletion in `options.output_unit`.
+///
+/// The vector only includes months when the depletion is greater than 0.001 units.
+/// The calculation stops if a negative depletion value is encountered, indicating complete aquifer depletion.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] under the same conditions as [`calculate_streamflow_depletion_series`].
+pub fn calculate_streamflow_depletion_with_options<M: DepletionModel + ?Sized>(
+    model: &M,
+    pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in options.pumping_unit / month
+    options: &DepletionOptions,
+) -> Result<Vec<(NaiveDate, f64)>, DepletionError> {
+    let pumping = MonthlySeries::from(pumping_volumes_monthly);
+    let monthly_depletion_amount = calculate_streamflow_depletion_series(model, &pumping, options)?;
+    Ok(create_results_vector(
+        monthly_depletion_amount.iter(),
+        &options.reporting,
+    ))
+}
+
+/// Calculates monthly streamflow depletion for any [`DepletionModel`] from a dense monthly series.
+///
+/// This is the same calculation as [`calculate_streamflow_depletion_with_options`] without
+/// converting from a HashMap or filtering the results.
+///
+/// # Parameters
+///
+/// * `model`: The depletion model supplying the depletion fraction over time.
+/// * `pumping`: The monthly pumping volumes in `options.pumping_unit`.
+/// * `options`: The horizon, units and method used for the calculation.
+///
+/// # Returns
+///
+/// A [`MonthlySeries`] of the months of the horizon starting in the first month of pumping,
+/// with the monthly streamflow depletion in `options.output_unit`.
+///
+/// # Errors
+///
+/// * [`DepletionError::EmptyInput`] if there is no pumping.
+/// * [`DepletionError::NonPhysicalParameter`] if a pumping volume is not finite, `days_per_month`
+///   is not greater than zero for [`Horizon::AverageMonths`], or [`DepletionModel::validate`]
+///   rejects the model.
+/// * [`DepletionError::InvalidDate`] if the pumping extends beyond the calendar or the end date
+///   of the horizon is before the first month of pumping.
+/// * [`DepletionError::HorizonOverflow`] if the horizon extends beyond the calendar.
+pub fn calculate_streamflow_depletion_series<M: DepletionModel + ?Sized>(
+    model: &M,
+    pumping: &MonthlySeries,
+    options: &DepletionOptions,
+) -> Result<MonthlySeries, DepletionError> {
+    model.validate()?;
+    let (total_months, total_days) = validate_horizon(pumping, options)?;
+
+    let monthly_depletion_amount = match options.method {
+        ConvolutionMethod::DailySuperposition | ConvolutionMethod::FftSuperposition => {
+            // convert the monthly volumes to daily rates, superimpose each day of pumping and sum
+            // the daily depletion amounts to monthly totals in the output unit
+            let pumping_rates_daily = pumping.to_daily();
+            let daily_depletion_amount =
+                daily_superposition(model, &pumping_rates_daily, total_days, options.method);
+            create_monthly_depletion(
+                &daily_depletion_amount,
+                options.pumping_unit,
+                options.output_unit,
+            )
+        }
+        ConvolutionMethod::MonthlyIntegrated => {
+            let mut monthly_depletion_amount =
+                monthly_integrated_depletion(model, pumping, total_months, total_days);
+            convert_units(
+                monthly_depletion_amount.values_mut(),
+                options.pumping_unit,
+                options.output_unit,
+            );
+            monthly_depletion_amount
+        }
+    };
+    Ok(monthly_depletion_amount.window(pumping.start_date(), total_months))
+}
+
+/// Calculates daily streamflow depletion for any [`DepletionModel`] from daily pumping.
+///
+/// Each day of pumping is superimposed on the model's daily unit response, as in the monthly
+/// calculation, without summing the result into months. Monthly pumping can be spread evenly
+/// over each month with [`MonthlySeries::to_daily`], and the daily result can be summed into
+/// months with [`DailySeries::to_monthly`], which gives the same totals as
+/// [`calculate_streamflow_depletion_series`].
+///
+/// [`ConvolutionMethod::FftSuperposition`] uses the fast Fourier transform. Any other method
+/// uses [`ConvolutionMethod::DailySuperposition`], since [`ConvolutionMethod::MonthlyIntegrated`]
+/// only gives monthly totals.
+///
+/// # Parameters
+///
+/// * `model`: The depletion model supplying the depletion fraction over time.
+/// * `pumping`: The volume pumped each day in `options.pumping_unit`.
+/// * `options`: The horizon, units and method used for the calculation.
+///
+/// # Returns
+///
+/// A [`DailySeries`] of the depletion for each day in `options.output_unit`, from the first day
+/// of the first month of pumping to the end of the last month of the horizon.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] under the same conditions as [`calculate_streamflow_depletion_series`].
+pub fn calculate_streamflow_depletion_daily<M: DepletionModel + ?Sized>(
+    model: &M,
+    pumping: &DailySeries,
+    options: &DepletionOptions,
+) -> Result<DailySeries, DepletionError> {
+    model.validate()?;
+    let monthly_pumping = pumping.to_monthly();
+    let (total_months, total_days) = validate_horizon(&monthly_pumping, options)?;
+
+    let mut daily_depletion_amount =
+        daily_superposition(model, pumping, total_days, options.method);
+    convert_units(
+        daily_depletion_amount.values_mut(),
+        options.pumping_unit,
+        options.output_unit,
+    );
+
+    let horizon = MonthlySeries::zeros(monthly_pumping.start_date(), total_months);
+    let horizon_days = horizon.month_bounds()[total_months] as usize;
+    Ok(daily_depletion_amount.window(horizon.start_date(), horizon_days))
+}
+
+/// Calculates streamflow depletion over arbitrary periods for any [`DepletionModel`].
+///
+/// Pumping and results can each be on any list of period boundaries, such as irrigation
+/// seasons, weeks or the stress periods of a groundwater model. Pumping is taken at a constant
+/// rate through each pumping period. With [`ConvolutionMethod::MonthlyIntegrated`] the depletion
+/// in each result period is calculated directly from the model's volume function; otherwise the
+/// pumping is spread into daily rates and superimposed on the daily unit response.
+///
+/// The horizon is the end of the last result period, so `options.horizon`,
+/// `options.days_per_month` and `options.total_months` are not used. As in
+/// [`calculate_streamflow_depletion_series`], every result period is returned without filtering,
+/// so `options.reporting` is not used either.
+///
+/// # Parameters
+///
+/// * `model`: The depletion model supplying the depletion fraction over time.
+/// * `pumping`: The volume pumped in each pumping period in `options.pumping_unit`.
+/// * `output_periods`: The result periods. Only their boundaries are used.
+/// * `options`: The units and method used for the calculation.
+///
+/// # Returns
+///
+/// A [`PeriodSeries`] with the periods of `output_periods` and the depletion in each period in
+/// `options.output_unit`. Periods that end before pumping begins have no depletion.
+///
+/// # Errors
+///
+/// * [`DepletionError::EmptyInput`] if there are no pumping periods.
+/// * [`DepletionError::NonPhysicalParameter`] if a pumping volume is not finite or
+///   [`DepletionModel::validate`] rejects the model.
+/// * [`DepletionError::HorizonOverflow`] if the response to the last pumping period, followed
+///   for the length of the horizon, extends beyond the calendar.
+pub fn calculate_streamflow_depletion_periods<M: DepletionModel + ?Sized>(
+    model: &M,
+    pumping: &PeriodSeries,
+    output_periods: &PeriodSeries,
+    options: &DepletionOptions,
+) -> Result<PeriodSeries, DepletionError> {
+    model.validate()?;
+    if pumping.is_empty() {
+        return Err(DepletionError::EmptyInput);
+    }
+    if let Some(value) = pumping.values().iter().find(|value| !value.is_finite()) {
+        return Err(DepletionError::NonPhysicalParameter {
+            name: "pumping volume",
+            value: *value,
+        });
+    }
+    let output_bounds: Vec<i64> = output_periods
+        .boundaries()
+        .iter()
+        .map(|boundary| (*boundary - pumping.start_date()).num_days())
+        .collect();
+    let horizon = output_bounds[output_periods.len()].max(0);
+    // the daily response to the last day of pumping is followed for the whole horizon
+    pumping
+        .end_date()
+        .checked_add_days(Days::new(horizon as u64 + 1))
+        .ok_or(DepletionError::HorizonOverflow {
+            total_months: MonthlySeries::zeros(pumping.start_date(), 0)
+                .month_index(output_periods.end_date())
+                .max(0) as usize,
+        })?;
+
+    let mut depletion_amount = output_periods.clone();
+    match options.method {
+        ConvolutionMethod::MonthlyIntegrated => {
+            depletion_amount
+                .values_mut()
+                .copy_from_slice(&period_integrated_depletion(
+                    model,
+                    &pumping.period_bounds(),
+                    pumping.values(),
+                    &output_bounds,
+                    horizon,
+                ));
+            convert_units(
+                depletion_amount.values_mut(),
+                options.pumping_unit,
+                options.output_unit,
+            );
+        }
+        _ => {
+            let pumping_rates_daily = pumping.to_daily();
+            let daily_depletion_amount = daily_superposition(
+                model,
+                &pumping_rates_daily,
+                horizon as usize,
+                options.method,
+            );
+            depletion_amount = daily_depletion_amount.to_periods(output_periods);
+            convert_units(
+                depletion_amount.values_mut(),
+                options.pumping_unit,
+                options.output_unit,
+            );
+        }
+    }
+    Ok(depletion_amount)
+}
+
+/// Checks that the pumping and the horizon can be calculated.
+///
+/// Every date used by the engine, up to the end of the response to the last month of pumping,
+/// must be within the calendar supported by [`NaiveDate`].
+///
+/// # Returns
+///
+/// The number of months of results and the number of days the response to each day of pumping
+/// is followed for.
+fn validate_horizon(
+    pumping: &MonthlySeries,
+    options: &DepletionOptions,
+) -> Result<(usize, usize), DepletionError> {
+    if pumping.is_empty() {
+        return Err(DepletionError::EmptyInput);
+    }
+    if let Some(value) = pumping.values().iter().find(|value| !value.is_finite()) {
+        return Err(DepletionError::NonPhysicalParameter {
+            name: "pumping volume",
+            value: *value,
+        });
+    }
+
+    let start_date = pumping.start_date();
+    let pumping_end = i32::try_from(pumping.len())
+        .ok()
+        .and_then(|months| add_months(start_date, months))
+        .ok_or(DepletionError::InvalidDate(start_date))?;
+    let total_months = match options.horizon {
+        Horizon::AverageMonths | Horizon::CalendarMonths => options.total_months,
+        Horizon::EndDate(end_date) => usize::try_from(pumping.month_index(end_date))
+            .map(|months| months + 1)
+            .map_err(|_| DepletionError::InvalidDate(end_date))?,
+    };
+    let horizon_overflow = DepletionError::HorizonOverflow { total_months };
+    let horizon_end = i32::try_from(total_months)
+        .ok()
+        .and_then(|months| add_months(start_date, months))
+        .ok_or(horizon_overflow.clone())?;
+
+    let total_days = match options.horizon {
+        Horizon::AverageMonths => {
+            check_positive("days per month", options.days_per_month)?;
+            (total_months as f64 * options.days_per_month).ceil()
+        }
+        Horizon::CalendarMonths | Horizon::EndDate(_) => {
+            (horizon_end - start_date).num_days() as f64
+        }
+    };
+    if total_days > i32::MAX as f64 {
+        return Err(horizon_overflow);
+    }
+    horizon_end
+        .checked_add_days(Days::new(1))
+        .and_then(|_| pumping_end.checked_add_days(Days::new(total_days as u64 + 1)))
+        .map(|_| (total_months, total_days as usize))
+        .ok_or(horizon_overflow)
+}
+
+/// Superimposes daily pumping rates on the model's unit response with the given method.
+///
+/// The unit response is evaluated for `total_days` days, so the depletion from each day of
+/// pumping is followed for that many days.
+fn daily_superposition<M: DepletionModel + ?Sized>(
+    model: &M,
+    pumping_rates_daily: &DailySeries,
+    total_days: usize,
+    method: ConvolutionMethod,
+) -> DailySeries {
+    let base_depletion_fraction = model.unit_response(total_days);
+    match method {
+        ConvolutionMethod::FftSuperposition => {
+            fft_daily_depletion(pumping_rates_daily, &base_depletion_fraction)
+        }
+        _ => daily_depletion(pumping_rates_daily, &base_depletion_fraction),
+    }
+}
+
+/// Converts volumes in place from `from` to `to`.
+fn convert_units(values: &mut [f64], from: VolumeUnit, to: VolumeUnit) {
+    let conversion = from.conversion_to(to);
+    for value in values {
+        *value *= conversion;
+    }
+}
+
+/// Calculates monthly streamflow depletion for each stream that a well depletes.
+///
+/// Some geometries, such as a well between two streams, split the pumping between several
+/// streams. Each stream is described by its own [`DepletionModel`] and is run through the same
+/// engine as [`calculate_streamflow_depletion`].
+///
+/// # Parameters
+///
+/// * `streams`: Pairs of a stream identifier and the depletion model for that stream.
+/// * `pumping_volumes_monthly`: A HashMap containing monthly pumping volumes in acre-ft/month, keyed by date.
+/// * `days_per_month`: The average number of days per month used in calculations.
+/// * `total_months`: The total number of months to calculate depletion for.
+///
+/// # Returns
+///
+/// A [`DepletionByStream`] where the keys are the stream identifiers and the values are the
+/// monthly depletion of that stream in acre-ft/month.
+///
+/// # Errors
+///
+/// * [`DepletionError::InconsistentParameters`] if two streams have the same identifier.
+/// * The first [`DepletionError`] from [`calculate_streamflow_depletion`] for any stream.
+pub fn calculate_streamflow_depletion_by_stream(
+    streams: &[(i32, &dyn DepletionModel)],
+    pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in acre-ft / month
+    days_per_month: f64,
+    total_months: usize,
+) -> Result<DepletionByStream, DepletionError> {
+    for (index, (stream, _)) in streams.iter().enumerate() {
+        if streams[..index].iter().any(|(other, _)| other == stream) {
+            return Err(DepletionError::InconsistentParameters {
+                name: "stream identifier",
+                value: *stream as f64,
+                other: "stream identifier",
+                other_value: *stream as f64,
+            });
+        }
+    }
+    streams
+        .iter()
+        .map(|(stream, model)| {
+            calculate_streamflow_depletion(
+                *model,
+                pumping_volumes_monthly,
+                days_per_month,
+                total_months,
+            )
+            .map(|depletion| (*stream, depletion))
+        })
+        .collect()
+}
+
+/// Returns the daily increments of a unit response, which is the response to a single day of pumping.
+fn response_increments(base_depletion_fraction: &[f64]) -> Vec<f64> {
+    base_depletion_fraction
+        .iter()
+        .enumerate()
+        .map(|(index, fraction)| {
+            if index == 0 {
+                *fraction
+            } else {
+                fraction - base_depletion_fraction[index - 1]
+            }
+        })
+        .collect()
+}
+
+/// Superimposes daily pumping rates on a unit response to get daily depletion amounts.
+///
+/// # Parameters
+///
+/// * `pumping_rates_daily`: A daily series of pumping rates in volume/day.
+/// * `base_depletion_fraction`: The model's depletion fraction for each day since pumping began.
+///
+/// # Returns
+///
+/// A [`DailySeries`] of the depletion amount for each day in the volume units of the pumping
+/// rates, starting the day after the first day of pumping.
+pub(crate) fn daily_depletion(
+    pumping_rates_daily: &DailySeries,
+    base_depletion_fraction: &[f64],
+) -> DailySeries {
+    if pumping_rates_daily.is_empty() || base_depletion_fraction.is_empty() {
+        return DailySeries::default();
+    }
+    let depletion_increments = response_increments(base_depletion_fraction);
+
+    // depletion is always the day after the pumping occurs
+    let mut daily_depletion_amount = DailySeries::zeros(
+        pumping_rates_daily.start_date() + Duration::days(1),
+        pumping_rates_daily.len() + depletion_increments.len() - 1,
+    );
+    let depletion_values = daily_depletion_amount.values_mut();
+    for (day, pumping_rate) in pumping_rates_daily.values().iter().enumerate() {
+        if *pumping_rate <= 0.0 {
+            continue;
+        }
+        // add the day depletion to the daily depletion amount for the corresponding date and forward
+        for (depletion_index, increment) in depletion_increments.iter().enumerate() {
+            depletion_values[day + depletion_index] += pumping_rate * increment;
+        }
+    }
+    daily_depletion_amount
+}
+
+/// Superimposes daily pumping rates on a unit response with the fast Fourier transform.
+///
+/// The pumping rates are convolved with the daily increments of the unit response. The result
+/// is the same as [`daily_depletion`] to round-off.
+///
+/// # Parameters
+///
+/// * `pumping_rates_daily`: A daily series of pumping rates in volume/day.
+/// * `base_depletion_fraction`: The model's depletion fraction for each day since pumping began.
+///
+/// # Returns
+///
+/// A [`DailySeries`] of the depletion amount for each day in the volume units of the pumping
+/// rates, starting the day after the first day of pumping.
+pub(crate) fn fft_daily_depletion(
+    pumping_rates_daily: &DailySeries,
+    base_depletion_fraction: &[f64],
+) -> DailySeries {
+    let pumping_rates: Vec<f64> = pumping_rates_daily
+        .values()
+        .iter()
+        .map(|pumping_rate| pumping_rate.max(0.0))
+        .collect();
+    let depletion_increments = response_increments(base_depletion_fraction);
+
+    // depletion is always the day after the pumping occurs
+    DailySeries::new(
+        pumping_rates_daily.start_date() + Duration::days(1),
+        convolve(&pumping_rates, &depletion_increments),
+    )
+}
+
+/// Calculates monthly depletion totals from the model's volume function.
+///
+/// Pumping is taken at a constant rate through each month. A month of pumping at rate `Q`
+/// from day `a` to day `b` depletes `Q [V(t - a) - V(t - b)]` by day `t`, where `V` is the
+/// depletion volume per unit pumping rate, so the depletion within each result month is the
+/// difference of this expression at the month's bounds. Volumes stay in the pumping units.
+///
+/// # Parameters
+///
+/// * `model`: The depletion model supplying the depletion volume over time.
+/// * `pumping`: The monthly pumping volumes.
+/// * `total_months`: The total number of months to calculate depletion for.
+/// * `response_days`: The number of days the response to pumping is followed for, as in the
+///   daily methods.
+///
+/// # Returns
+///
+/// A [`MonthlySeries`] of `total_months` months starting in the first month of pumping, with
+/// the total monthly depletion amount in the units of the pumping volumes.
+pub(crate) fn monthly_integrated_depletion<M: DepletionModel + ?Sized>(
+    model: &M,
+    pumping: &MonthlySeries,
+    total_months: usize,
+    response_days: usize,
+) -> MonthlySeries {
+    let monthly_depletion_amount = MonthlySeries::zeros(pumping.start_date(), total_months);
+    MonthlySeries::new(
+        pumping.start_date(),
+        period_integrated_depletion(
+            model,
+            &pumping.month_bounds(),
+            pumping.values(),
+            &monthly_depletion_amount.month_bounds(),
+            response_days as i64,
+        ),
+    )
+}
+
+/// Calculates depletion totals over arbitrary periods from the model's volume function.
+///
+/// This is the calculation behind [`monthly_integrated_depletion`] for any pumping and result
+/// periods. Pumping is taken at a constant rate through each pumping period.
+///
+/// As in [`daily_superposition`], the response to pumping is only followed for `response_days`
+/// days. After that the depletion fraction of the pumping is held at its value on the last day
+/// followed, so that pumping adds no more depletion to later periods.
+///
+/// # Parameters
+///
+/// * `model`: The depletion model supplying the depletion volume over time.
+/// * `pumping_bounds`: The days from the start of pumping to each pumping period boundary, starting at zero.
+/// * `pumping_volumes`: The volume pumped in each pumping period.
+/// * `output_bounds`: The days from the start of pumping to each result period boundary, which may be negative.
+/// * `response_days`: The number of days the response to pumping is followed for.
+///
+/// # Returns
+///
+/// A Vec with the total depletion amount in each result period in the units of the pumping volumes.
+pub(crate) fn period_integrated_depletion<M: DepletionModel + ?Sized>(
+    model: &M,
+    pumping_bounds: &[i64],
+    pumping_volumes: &[f64],
+    output_bounds: &[i64],
+    response_days: i64,
+) -> Vec<f64> {
+    let mut depletion_values = vec![0.0; output_bounds.len().saturating_sub(1)];
+    let horizon = output_bounds.last().copied().unwrap_or(0).max(0);
+    if response_days <= 0 {
+        return depletion_values;
+    }
+
+    // depletion is always the day after the pumping occurs, so the response is shifted by a day
+    let volume = model.volume_response(horizon.min(response_days) as usize);
+    let last_day = response_days - 1;
+    let last_fraction = if response_days < horizon {
+        model.depletion_fraction(last_day as f64)
+    } else {
+        0.0
+    };
+    let shifted_volume = |days: i64| {
+        let day = days - 1;
+        if day <= 0 {
+            0.0
+        } else if day <= last_day {
+            volume[day as usize]
+        } else {
+            volume[last_day as usize] + (day - last_day) as f64 * last_fraction
+        }
+    };
+
+    for (pumping_period, pumping_volume) in pumping_volumes.iter().enumerate() {
+        let pumping_start = pumping_bounds[pumping_period];
+        let pumping_end = pumping_bounds[pumping_period + 1];
+        if *pumping_volume <= 0.0 || pumping_start >= horizon {
+            continue;
+        }
+        let pumping_rate = pumping_volume / (pumping_end - pumping_start) as f64;
+        let pumped_by =
+            |days: i64| shifted_volume(days - pumping_start) - shifted_volume(days - pumping_end);
+
+        // result periods that end before the pumping starts have no depletion from it
+        let first_period = output_bounds[1..].partition_point(|bound| *bound <= pumping_start);
+        for period in first_period..depletion_values.len() {
+            depletion_values[period] += pumping_rate
+                * (pumped_by(output_bounds[period + 1]) - pumped_by(output_bounds[period]));
+        }
+    }
+    depletion_values
+}
+
+/// Aggregates daily depletion amounts into monthly totals and converts units.
+///
+/// This function takes daily streamflow depletion amounts and aggregates them into
+/// monthly totals. It also converts the units from `depletion_unit` to `output_unit`.
+///
+/// # Parameters
+///
+/// * `daily_depletion_amount`: A daily series of the depletion amount.
+/// * `depletion_unit`: The unit of the daily depletion amounts.
+/// * `output_unit`: The unit of the monthly totals.
+///
+/// # Returns
+///
+/// A [`MonthlySeries`] of the total monthly depletion amount in `output_unit`.
+pub(crate) fn create_monthly_depletion(
+    daily_depletion_amount: &DailySeries,
+    depletion_unit: VolumeUnit,
+    output_unit: VolumeUnit,
+) -> MonthlySeries {
+    let mut monthly_depletion_amount = daily_depletion_amount.to_monthly();
+    convert_units(
+        monthly_depletion_amount.values_mut(),
+        depletion_unit,
+        output_unit,
+    );
+    monthly_depletion_amount
+}
+
+/// Creates a vector of streamflow depletion results.
+///
+/// This function processes depletion amounts for consecutive periods, such as months, and
+/// generates a vector of results, filtering out insignificant depletion values and handling
+/// negative values as set by `reporting`.
+///
+/// # Parameters
+///
+/// * `depletion_amount`: The start date of each period and its depletion amount, in date order.
+/// * `reporting`: The threshold, zero filling and negative value handling.
+///
+/// # Returns
+///
+/// A Vec of tuples, where each tuple contains:
+/// * A NaiveDate representing the start of a period.
+/// * An f64 value representing the streamflow depletion for that period.
+///
+/// With the default options the vector only includes periods when the depletion is greater
+/// than 0.001 units, and stops if a negative depletion value is encountered, indicating
+/// complete aquifer depletion. With zero filling every period is included, and the periods
+/// after a stop are given as zero.
+pub(crate) fn create_results_vector(
+    depletion_amount: impl IntoIterator<Item = (NaiveDate, f64)>,
+    reporting: &ReportingOptions,
+) -> Vec<(NaiveDate, f64)> {
+    let mut results: Vec<(NaiveDate, f64)> = vec![];
+    let mut stopped = false;
+    for (result_date, monthly_depletion) in depletion_amount {
+        let monthly_depletion = match reporting.negative_handling {
+            _ if stopped => 0.0,
+            NegativeHandling::Stop if monthly_depletion < 0.0 => {
+                // The depletion is negative, which means the aquifer has been depleted completely,
+                // so we should stop the simulation and return the results.
+                if !reporting.zero_fill {
+                    break;
+                }
+                stopped = true;
+                0.0
+            }
+            NegativeHandling::Clamp => monthly_depletion.max(0.0),
+            _ => monthly_depletion,
+        };
+
+        if monthly_depletion.abs() > reporting.threshold {
+            results.push((result_date, monthly_depletion));
+        } else if reporting.zero_fill {
+            results.push((result_date, 0.0));
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glover_alluvial::GloverAlluvial;
+    use crate::glover_infinite::GloverInfinite;
+    use crate::sdf::StreamDepletionFactor;
+    use crate::utils::add_months;
+    use crate::{
+        calculate_streamflow_depletion_alluvial, calculate_streamflow_depletion_infinite,
+        calculate_streamflow_depletion_sdf,
+    };
+
+    #[test]
+    fn test_boxed_models_match_method_functions() {
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0);
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(), 50.0);
+        let days_per_month = 30.42;
+        let total_months = 36;
+        let t = 261_800.0 / 7.481;
+
+        let models: Vec<Box<dyn DepletionModel>> = vec![
+            Box::new(GloverInfinite::new(4000.0, 0.2, t)),
+            Box::new(GloverAlluvial::new(4000.0, 8000.0, 0.2, t)),
+            Box::new(StreamDepletionFactor::new(265.0)),
+        ];
+        let expected = [
+            calculate_streamflow_depletion_infinite(
+                &pumping_volumes,
+                4000.0,
+                0.2,
+                t,
+                days_per_month,
+                total_months,
+            )
+            .unwrap(),
+            calculate_streamflow_depletion_alluvial(
+                &pumping_volumes,
+                4000.0,
+                8000.0,
+                0.2,
+                t,
+                days_per_month,
+                total_months,
+            )
+            .unwrap(),
+            calculate_streamflow_depletion_sdf(
+                &pumping_volumes,
+                265,
+                days_per_month,
+                total_months as u32,
+            )
+            .unwrap(),
+        ];
+
+        for (model, expected) in models.iter().zip(expected.iter()) {
+            let value = calculate_streamflow_depletion(
+                model.as_ref(),
+                &pumping_volumes,
+                days_per_month,
+                total_months,
+            )
+            .unwrap();
+            assert_eq!(value.len(), expected.len());
+            for (actual, expected) in value.iter().zip(expected.iter()) {
+                assert_eq!(actual.0, expected.0);
+                assert!((actual.1 - expected.1).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_monthly_integrated_matches_daily() {
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0);
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(), 50.0);
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2026, 7, 1).unwrap(), 75.0);
+        let pumping = MonthlySeries::from(&pumping_volumes);
+        let t = 261_800.0 / 7.481;
+
+        let models: Vec<Box<dyn DepletionModel>> = vec![
+            Box::new(GloverInfinite::new(4000.0, 0.2, t)),
+            Box::new(GloverAlluvial::new(4000.0, 8000.0, 0.2, t)),
+            Box::new(StreamDepletionFactor::new(265.0)),
+            Box::new(crate::hunt_1999::Hunt1999::new(4000.0, 0.2, t, 20.0)),
+        ];
+        // short average months end the response to early pumping before the end of the horizon
+        for days_per_month in [30.42, 28.0] {
+            let daily = DepletionOptions::new(days_per_month, 60);
+            let monthly = daily.with_method(ConvolutionMethod::MonthlyIntegrated);
+            for model in &models {
+                let value =
+                    calculate_streamflow_depletion_series(model, &pumping, &monthly).unwrap();
+                let daily_depletion =
+                    calculate_streamflow_depletion_daily(model, &pumping.to_daily(), &daily)
+                        .unwrap();
+                // the daily method lags by about half a day, so each month is compared with the
+                // average of the daily depletion and the daily depletion a day later
+                let days = daily_depletion.values();
+                let bounds = value.month_bounds();
+                for month in 0..value.len() - 1 {
+                    let expected: f64 = (bounds[month]..bounds[month + 1])
+                        .map(|day| 0.5 * (days[day as usize] + days[day as usize + 1]))
+                        .sum();
+                    assert!((value.values()[month] - expected).abs() < 0.005);
+                }
+            }
+        }
+
+        // The first month is the exact integral of the shifted response, while the daily method
+        // takes the fraction at the start of each day and lags by about half a day of pumping.
+        let model = GloverInfinite::new(4000.0, 0.2, t);
+        let daily = DepletionOptions::new(30.42, 60);
+        let monthly = daily.with_method(ConvolutionMethod::MonthlyIntegrated);
+        let rate = 100.0 / 31.0;
+        let expected =
+            calculate_streamflow_depletion_with_options(&model, &pumping_volumes, &daily).unwrap()
+                [0]
+            .1;
+        let value = calculate_streamflow_depletion_with_options(&model, &pumping_volumes, &monthly)
+            .unwrap()[0]
+            .1;
+        assert!((value - rate * model.depletion_volume(30.0)).abs() < 1e-9);
+        let half_day_lag = 0.5 * rate * model.depletion_fraction(30.0);
+        assert!((value - expected - half_day_lag).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_fft_matches_daily_superposition() {
+        // 4 years of monthly pumping with a month off each year
+        let start_date = NaiveDate::from_ymd_opt(1965, 1, 1).unwrap();
+        let pumping_volumes: HashMap<NaiveDate, f64> = (0..48)
+            .filter(|month| month % 12 != 1)
+            .map(|month| {
+                let volume = 40.0 + 30.0 * ((month % 12) as f64 / 2.0).sin();
+                (add_months(start_date, month).unwrap(), volume)
+            })
+            .collect();
+        let t = 261_800.0 / 7.481;
+        let daily = DepletionOptions::new(30.42, 72);
+        let fft = daily.with_method(ConvolutionMethod::FftSuperposition);
+
+        let models: Vec<Box<dyn DepletionModel>> = vec![
+            Box::new(GloverInfinite::new(4000.0, 0.2, t)),
+            Box::new(GloverAlluvial::new(1000.0, 3000.0, 0.2, t)),
+        ];
+        for model in models {
+            let expected =
+                calculate_streamflow_depletion_with_options(&model, &pumping_volumes, &daily)
+                    .unwrap();
+            let value = calculate_streamflow_depletion_with_options(&model, &pumping_volumes, &fft)
+                .unwrap();
+            assert_eq!(value.len(), expected.len());
+            for (actual, expected) in value.iter().zip(expected.iter()) {
+                assert_eq!(actual.0, expected.0);
+                assert!((actual.1 - expected.1).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pumping_and_output_units() {
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0);
+        let model = GloverInfinite::new(4000.0, 0.2, 261_800.0 / 7.481);
+        let options = DepletionOptions::new(30.42, 24);
+        let expected =
+            calculate_streamflow_depletion_with_options(&model, &pumping_volumes, &options)
+                .unwrap();
+
+        let cubic_meters = VolumeUnit::AcreFeet.conversion_to(VolumeUnit::CubicMeters);
+        for method in [
+            ConvolutionMethod::DailySuperposition,
+            ConvolutionMethod::MonthlyIntegrated,
+        ] {
+            let options = options
+                .with_method(method)
+                .with_output_unit(VolumeUnit::CubicMeters);
+            let baseline = calculate_streamflow_depletion_with_options(
+                &model,
+                &pumping_volumes,
+                &options.with_output_unit(VolumeUnit::AcreFeet),
+            )
+            .unwrap();
+            let value =
+                calculate_streamflow_depletion_with_options(&model, &pumping_volumes, &options)
+                    .unwrap();
+            assert_eq!(value.len(), baseline.len());
+            for (actual, expected) in value.iter().zip(baseline.iter()) {
+                assert!((actual.1 / cubic_meters - expected.1).abs() < 1e-9);
+            }
+        }
+
+        // the same pumping given in cfs-days gives the same depletion in acre-ft
+        let mut pumping_cfs_days = HashMap::new();
+        pumping_cfs_days.insert(
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            100.0 * VolumeUnit::AcreFeet.conversion_to(VolumeUnit::CfsDays),
+        );
+        let value = calculate_streamflow_depletion_with_options(
+            &model,
+            &pumping_cfs_days,
+            &options.with_pumping_unit(VolumeUnit::CfsDays),
+        )
+        .unwrap();
+        for (actual, expected) in value.iter().zip(expected.iter()) {
+            assert!((actual.1 - expected.1).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_metric_model_matches_us_customary() {
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0);
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(), 40.0);
+        let t = 261_800.0 / 7.481;
+        let expected = calculate_streamflow_depletion_alluvial(
+            &pumping_volumes,
+            4000.0,
+            8000.0,
+            0.2,
+            t,
+            30.42,
+            36,
+        )
+        .unwrap();
+
+        let cubic_meters = VolumeUnit::AcreFeet.conversion_to(VolumeUnit::CubicMeters);
+        let pumping_cubic_meters: HashMap<NaiveDate, f64> = pumping_volumes
+            .iter()
+            .map(|(date, volume)| (*date, volume * cubic_meters))
+            .collect();
+        let meters = 1.0 / crate::units::LengthUnit::Meters.feet();
+        let model = GloverAlluvial::new(4000.0 * meters, 8000.0 * meters, 0.2, t * meters * meters);
+        let value = calculate_streamflow_depletion_with_options(
+            &model,
+            &pumping_cubic_meters,
+            &DepletionOptions::metric(30.42, 36),
+        )
+        .unwrap();
+
+        assert_eq!(value.len(), expected.len());
+        for (actual, expected) in value.iter().zip(expected.iter()) {
+            assert_eq!(actual.0, expected.0);
+            assert!((actual.1 / cubic_meters - expected.1).abs() < 1e-9 * expected.1.max(1.0));
+        }
+    }
+
+    #[test]
+    fn test_daily_depletion_sums_to_monthly() {
+        let mut pumping = MonthlySeries::zeros(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 3);
+        pumping.values_mut().copy_from_slice(&[100.0, 0.0, 50.0]);
+        let model = GloverInfinite::new(4000.0, 0.2, 261_800.0 / 7.481);
+        let options = DepletionOptions::new(30.42, 24);
+
+        let expected = calculate_streamflow_depletion_series(&model, &pumping, &options).unwrap();
+        let daily =
+            calculate_streamflow_depletion_daily(&model, &pumping.to_daily(), &options).unwrap();
+        assert_eq!(daily.start_date(), pumping.start_date());
+        assert_eq!(daily.len(), expected.month_bounds()[24] as usize);
+        let monthly = daily.to_monthly();
+        for (actual, expected) in monthly.values().iter().zip(expected.values()) {
+            assert!((actual - expected).abs() < 1e-9);
+        }
+
+        // a single day of pumping depletes the stream from the next day
+        let mut metered = DailySeries::default();
+        metered.add(NaiveDate::from_ymd_opt(2025, 1, 10).unwrap(), 1.0);
+        let daily = calculate_streamflow_depletion_daily(&model, &metered, &options).unwrap();
+        assert_eq!(
+            daily.get(NaiveDate::from_ymd_opt(2025, 1, 10).unwrap()),
+            Some(0.0)
+        );
+        assert!(
+            daily
+                .get(NaiveDate::from_ymd_opt(2025, 1, 11).unwrap())
+                .unwrap()
+                >= 0.0
+        );
+        assert!(daily.values().iter().sum::<f64>() < 1.0);
+    }
+
+    #[test]
+    fn test_periods_match_monthly_series() {
+        let mut pumping = MonthlySeries::zeros(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 3);
+        pumping.values_mut().copy_from_slice(&[100.0, 0.0, 50.0]);
+        let model = GloverAlluvial::new(4000.0, 8000.0, 0.2, 261_800.0 / 7.481);
+        let output_periods = PeriodSeries::from(&MonthlySeries::zeros(pumping.start_date(), 24));
+
+        for method in [
+            ConvolutionMethod::DailySuperposition,
+            ConvolutionMethod::MonthlyIntegrated,
+        ] {
+            // the daily response is followed to the end of the horizon rather than for 30.42 days a month
+            let options = DepletionOptions::new(31.0, 24).with_method(method);
+            let expected =
+                calculate_streamflow_depletion_series(&model, &pumping, &options).unwrap();
+            let value = calculate_streamflow_depletion_periods(
+                &model,
+                &PeriodSeries::from(&pumping),
+                &output_periods,
+                &options,
+            )
+            .unwrap();
+            assert_eq!(value.boundaries(), output_periods.boundaries());
+            for (actual, expected) in value.values().iter().zip(expected.values()) {
+                assert!((actual - expected).abs() < 1e-9);
+            }
+        }
+
+        assert_eq!(
+            calculate_streamflow_depletion_periods(
+                &model,
+                &PeriodSeries::from(&MonthlySeries::default()),
+                &output_periods,
+                &DepletionOptions::new(30.42, 0),
+            ),
+            Err(DepletionError::EmptyInput)
+        );
+
+        // results far in the future would follow the response beyond the calendar
+        let far_future = PeriodSeries::zeros(vec![pumping.start_date(), NaiveDate::MAX]).unwrap();
+        assert!(matches!(
+            calculate_streamflow_depletion_periods(
+                &model,
+                &PeriodSeries::from(&pumping),
+                &far_future,
+                &DepletionOptions::new(30.42, 0),
+            ),
+            Err(DepletionError::HorizonOverflow { .. })
+        ));
+    }
+
+    #[test]
+    fn test_seasonal_pumping_with_weekly_results() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        // pumping through the irrigation season and weekly accounting from the following spring
+        let seasons = PeriodSeries::new(
+            vec![date(2025, 4, 15), date(2025, 10, 15), date(2026, 4, 15)],
+            vec![300.0, 0.0],
+        )
+        .unwrap();
+        let weeks = PeriodSeries::weekly(date(2026, 3, 2), 8).unwrap();
+        let model = GloverInfinite::new(4000.0, 0.2, 261_800.0 / 7.481);
+
+        let daily = calculate_streamflow_depletion_periods(
+            &model,
+            &seasons,
+            &weeks,
+            &DepletionOptions::new(30.42, 0),
+        )
+        .unwrap();
+        let integrated = calculate_streamflow_depletion_periods(
+            &model,
+            &seasons,
+            &weeks,
+            &DepletionOptions::new(30.42, 0).with_method(ConvolutionMethod::MonthlyIntegrated),
+        )
+        .unwrap();
+        assert_eq!(daily.len(), 8);
+        for (daily, integrated) in daily.values().iter().zip(integrated.values()) {
+            assert!(*daily > 0.0);
+            assert!((daily - integrated).abs() < 0.01 * daily);
+        }
+    }
+
+    #[test]
+    fn test_calendar_horizon() {
+        let mut pumping = MonthlySeries::zeros(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 12);
+        pumping.values_mut().fill(10.0);
+        let model = GloverInfinite::new(4000.0, 0.2, 261_800.0 / 7.481);
+
+        let calendar = calculate_streamflow_depletion_series(
+            &model,
+            &pumping,
+            &DepletionOptions::calendar_months(24),
+        )
+        .unwrap();
+        assert_eq!(calendar.len(), 24);
+        // a long enough average month follows the response past the end of the horizon
+        let long_months = calculate_streamflow_depletion_series(
+            &model,
+            &pumping,
+            &DepletionOptions::new(31.0, 24),
+        )
+        .unwrap();
+        assert_eq!(calendar, long_months);
+        // a short average month truncates the response in the last month
+        let short_months = calculate_streamflow_depletion_series(
+            &model,
+            &pumping,
+            &DepletionOptions::new(28.0, 24),
+        )
+        .unwrap();
+        assert!(short_months.values()[23] < calendar.values()[23]);
+
+        let until = calculate_streamflow_depletion_series(
+            &model,
+            &pumping,
+            &DepletionOptions::until(NaiveDate::from_ymd_opt(2026, 12, 15).unwrap()),
+        )
+        .unwrap();
+        assert_eq!(until, calendar);
+
+        let before_pumping = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        assert_eq!(
+            calculate_streamflow_depletion_series(
+                &model,
+                &pumping,
+                &DepletionOptions::until(before_pumping)
+            ),
+            Err(DepletionError::InvalidDate(before_pumping))
+        );
+    }
+
+    #[test]
+    fn test_reporting_options() {
+        let start_date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let series = MonthlySeries::new(start_date, vec![5.0, 0.0005, 2.0, -1e-9, 0.5]);
+        let dates: Vec<NaiveDate> = (0..5).map(|index| series.date(index).unwrap()).collect();
+
+        let legacy = create_results_vector(series.iter(), &ReportingOptions::default());
+        assert_eq!(legacy, vec![(dates[0], 5.0), (dates[2], 2.0)]);
+
+        let clamped = ReportingOptions::default().with_negative_handling(NegativeHandling::Clamp);
+        assert_eq!(
+            create_results_vector(series.iter(), &clamped),
+            vec![(dates[0], 5.0), (dates[2], 2.0), (dates[4], 0.5)]
+        );
+        assert_eq!(
+            create_results_vector(series.iter(), &clamped.with_zero_fill(true)),
+            vec![
+                (dates[0], 5.0),
+                (dates[1], 0.0),
+                (dates[2], 2.0),
+                (dates[3], 0.0),
+                (dates[4], 0.5)
+            ]
+        );
+        assert_eq!(
+            create_results_vector(
+                series.iter(),
+                &ReportingOptions::default().with_zero_fill(true)
+            )
+            .iter()
+            .map(|(_, value)| *value)
+            .collect::<Vec<f64>>(),
+            vec![5.0, 0.0, 2.0, 0.0, 0.0]
+        );
+        assert_eq!(
+            create_results_vector(series.iter(), &ReportingOptions::dense()),
+            Vec::<(NaiveDate, f64)>::from(&series)
+        );
+    }
+
+    #[test]
+    fn test_invalid_input_returns_error() {
+        let model = GloverInfinite::new(4000.0, 0.2, 261_800.0 / 7.481);
+        let mut pumping_volumes = HashMap::new();
+        assert_eq!(
+            calculate_streamflow_depletion(&model, &pumping_volumes, 30.42, 12),
+            Err(DepletionError::EmptyInput)
+        );
+
+        // a start date late in the month is accepted and reported by month
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(), 100.0);
+        let value = calculate_streamflow_depletion(&model, &pumping_volumes, 30.42, 12).unwrap();
+        assert_eq!(value[0].0, NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+
+        let negative = GloverInfinite::new(4000.0, 0.2, -1.0);
+        assert_eq!(
+            calculate_streamflow_depletion(&negative, &pumping_volumes, 30.42, 12),
+            Err(DepletionError::NonPhysicalParameter {
+                name: "transmissivity",
+                value: -1.0
+            })
+        );
+        assert!(matches!(
+            calculate_streamflow_depletion(&model, &pumping_volumes, 0.0, 12),
+            Err(DepletionError::NonPhysicalParameter { .. })
+        ));
+        assert_eq!(
+            calculate_streamflow_depletion(&model, &pumping_volumes, 30.42, usize::MAX),
+            Err(DepletionError::HorizonOverflow {
+                total_months: usize::MAX
+            })
+        );
+
+        let mut late_pumping = HashMap::new();
+        late_pumping.insert(NaiveDate::MAX, 100.0);
+        assert!(matches!(
+            calculate_streamflow_depletion(&model, &late_pumping, 30.42, 12),
+            Err(DepletionError::InvalidDate(_))
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+use chrono::NaiveDate;
+use std::fmt;
+
+/// The errors that can occur while calculating stream depletion.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DepletionError {
+    /// No pumping volumes were given.
+    EmptyInput,
+    /// A date, or a date derived from it, is outside the range of the calendar.
+    InvalidDate(NaiveDate),
+    /// A parameter is outside its physical range, such as a negative transmissivity.
+    NonPhysicalParameter {
+        /// The name of the parameter.
+        name: &'static str,
+        /// The value that was given.
+        value: f64,
+    },
+    /// Two parameters are each possible but not together, such as a well beyond the aquifer boundary.
+    InconsistentParameters {
+        /// The name of the parameter.
+        name: &'static str,
+        /// The value that was given.
+        value: f64,
+        /// The name of the parameter it conflicts with.
+        other: &'static str,
+        /// The value of the parameter it conflicts with.
+        other_value: f64,
+    },
+    /// A period boundary is not after the previous boundary.
+    UnorderedPeriods(NaiveDate),
+    /// The depletion under constant pumping never reaches the target fraction of the pumping.
+    TargetNotReached {
+        /// The fraction of the pumping that was requested.
+        target: f64,
+        /// The fraction of the pumping the depletion approaches after a very long time.
+        ultimate_fraction: f64,
+    },
+    /// The simulation horizon is too long to be represented.
+    HorizonOverflow {
+        /// The number of months requested.
+        total_months: usize,
+    },
+}
+
+impl fmt::Display for DepletionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DepletionError::EmptyInput => write!(f, "no pumping volumes were given"),
+            DepletionError::InvalidDate(date) => {
+                write!(f, "the date {date} is outside the supported calendar")
+            }
+            DepletionError::NonPhysicalParameter { name, value } => {
+                write!(f, "the {name} of {value} is not physically possible")
+            }
+            DepletionError::InconsistentParameters {
+                name,
+                value,
+                other,
+                other_value,
+            } => write!(
+                f,
+                "the {name} of {value} is inconsistent with the {other} of {other_value}"
+            ),
+            DepletionError::UnorderedPeriods(date) => {
+                write!(
+                    f,
+                    "the period boundary {date} is not after the previous boundary"
+                )
+            }
+            DepletionError::TargetNotReached {
+                target,
+                ultimate_fraction,
+            } => write!(
+                f,
+                "the depletion never reaches the target of {target} of the pumping; it approaches {ultimate_fraction}"
+            ),
+            DepletionError::HorizonOverflow { total_months } => {
+                write!(
+                    f,
+                    "a horizon of {total_months} months cannot be represented"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for DepletionError {}
+
+/// Checks that a parameter is finite and greater than zero.
+pub(crate) fn check_positive(name: &'static str, value: f64) -> Result<(), DepletionError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(())
+    } else {
+        Err(DepletionError::NonPhysicalParameter { name, value })
+    }
+}
+
+/// Checks that a parameter is not negative or NaN. Infinity is allowed, for example for an unbounded aquifer.
+pub(crate) fn check_non_negative(name: &'static str, value: f64) -> Result<(), DepletionError> {
+    if value >= 0.0 {
+        Ok(())
+    } else {
+        Err(DepletionError::NonPhysicalParameter { name, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parameter_checks() {
+        assert!(check_positive("transmissivity", 1000.0).is_ok());
+        assert_eq!(
+            check_positive("transmissivity", 0.0),
+            Err(DepletionError::NonPhysicalParameter {
+                name: "transmissivity",
+                value: 0.0
+            })
+        );
+        assert!(check_positive("specific yield", f64::NAN).is_err());
+        assert!(check_non_negative("distance to boundary", f64::INFINITY).is_ok());
+        assert!(check_non_negative("distance to boundary", -1.0).is_err());
+        assert_eq!(
+            DepletionError::NonPhysicalParameter {
+                name: "transmissivity",
+                value: -5.0
+            }
+            .to_string(),
+            "the transmissivity of -5 is not physically possible"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use rustfft::FftPlanner;
+use rustfft::num_complex::Complex;
+
+/// Calculates the linear convolution of two dense arrays using the fast Fourier transform.
+///
+/// Superimposing a pumping record on a response directly takes time proportional to the product
+/// of their lengths. Both arrays are zero-padded to the full length of the result, transformed,
+/// multiplied and transformed back, which takes time proportional to `n log n`.
+///
+/// # Parameters
+///
+/// * `signal`: The first array, such as daily pumping rates.
+/// * `kernel`: The second array, such as the daily increments of a unit response.
+///
+/// # Returns
+///
+/// A Vec of length `signal.len() + kernel.len() - 1` where element `n` is the sum of
+/// `signal[j] * kernel[n - j]` over all `j`, or an empty Vec if either array is empty.
+pub(crate) fn convolve(signal: &[f64], kernel: &[f64]) -> Vec<f64> {
+    if signal.is_empty() || kernel.is_empty() {
+        return Vec::new();
+    }
+    let output_len = signal.len() + kernel.len() - 1;
+    let fft_len = output_len.next_power_of_two();
+
+    let mut planner = FftPlanner::new();
+    let forward = planner.plan_fft_forward(fft_len);
+    let inverse = planner.plan_fft_inverse(fft_len);
+
+    let padded = |values: &[f64]| {
+        let mut buffer: Vec<Complex<f64>> = values
+            .iter()
+            .map(|value| Complex::new(*value, 0.0))
+            .collect();
+        buffer.resize(fft_len, Complex::new(0.0, 0.0));
+        buffer
+    };
+    let mut signal_spectrum = padded(signal);
+    let mut kernel_spectrum = padded(kernel);
+    forward.process(&mut signal_spectrum);
+    forward.process(&mut kernel_spectrum);
+
+    for (value, kernel_value) in signal_spectrum.iter_mut().zip(kernel_spectrum.iter()) {
+        *value *= kernel_value;
+    }
+    inverse.process(&mut signal_spectrum);
+
+    // the inverse transform is not normalized
+    signal_spectrum
+        .iter()
+        .take(output_len)
+        .map(|value| value.re / fft_len as f64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convolve_matches_direct_sum() {
+        let signal: Vec<f64> = (0..37).map(|i| ((i * 7) % 11) as f64 - 3.0).collect();
+        let kernel: Vec<f64> = (0..100).map(|i| (-(i as f64) / 20.0).exp()).collect();
+
+        let value = convolve(&signal, &kernel);
+        assert_eq!(value.len(), signal.len() + kernel.len() - 1);
+        for (n, actual) in value.iter().enumerate() {
+            let expected: f64 = (0..signal.len())
+                .filter(|j| n >= *j && n - j < kernel.len())
+                .map(|j| signal[j] * kernel[n - j])
+                .sum();
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        assert!(convolve(&[], &kernel).is_empty());
+    }
+}
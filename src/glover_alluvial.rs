@@ -1,8 +1,22 @@
-use crate::utils::add_months;
-use chrono::{Datelike, NaiveDate};
+use crate::anisotropy::Anisotropy;
+use crate::engine::calculate_streamflow_depletion;
+use crate::error::{DepletionError, check_non_negative};
+use crate::model::DepletionModel;
+use crate::params::AquiferParams;
+use crate::units::{Length, LengthUnit};
+use crate::utils::erfc_integral;
+use chrono::NaiveDate;
 use scirs2_special::erfc;
 use std::collections::HashMap;
 
+/// The distance from an image well to the stream, in diffusion lengths, beyond which the image
+/// is left out of the depletion fraction.
+const FRACTION_CUTOFF: f64 = 2.9;
+/// The distance beyond which an image is left out of the depletion volume. The volume is the
+/// time multiplied by the sum of the images, so a larger cutoff keeps the part left out
+/// negligible and the volume continuous as images cross it.
+const VOLUME_CUTOFF: f64 = 6.0;
+
 /// Calculates streamflow depletion for an alluvial aquifer based on monthly pumping volumes.
 ///
 /// This function computes the streamflow depletion over time in an alluvial aquifer setting,
@@ -27,6 +41,11 @@ use std::collections::HashMap;
 ///
 /// The vector only includes months when the depletion is greater than 0.001 acre-ft/month.
 /// The calculation stops if a negative depletion value is encountered, indicating complete aquifer depletion.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] if the pumping, parameters or horizon are invalid; see
+/// [`calculate_streamflow_depletion`].
 pub fn calculate_streamflow_depletion_alluvial(
     pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in acre-ft / month
     distance_to_well: f64,
@@ -35,228 +54,513 @@ pub fn calculate_streamflow_depletion_alluvial(
     transmissivity: f64,
     days_per_month: f64,
     total_months: usize,
-) -> Vec<(NaiveDate, f64)> {
-    let total_days = (total_months as f64 * days_per_month).ceil() as usize;
+) -> Result<Vec<(NaiveDate, f64)>, DepletionError> {
+    let model = GloverAlluvial::new(
+        distance_to_well,
+        distance_to_boundary,
+        specific_yield,
+        transmissivity,
+    );
+    calculate_streamflow_depletion(
+        &model,
+        pumping_volumes_monthly,
+        days_per_month,
+        total_months,
+    )
+}
 
-    // 1. calculate the depletion fraction for each time step
-    let mut base_depletion_fraction = vec![0.0; total_days];
-    for m in 0..total_days {
-        base_depletion_fraction[m] = calculate_depletion_fraction_alluvial_aquifer(
+/// Calculates streamflow depletion for a well in a valley with parallel impermeable boundaries.
+///
+/// This function resolves the valley geometry into the alluvial image-well solution for the side
+/// of the stream the well is located on, see [`GloverAlluvial::from_geometry`].
+///
+/// # Parameters
+///
+/// * `pumping_volumes_monthly`: A HashMap containing monthly pumping volumes (in acre-ft/month)
+///   indexed by their corresponding dates.
+/// * `geometry`: The positions of the stream, the valley walls and the well (in feet).
+/// * `specific_yield`: The specific yield of the aquifer (dimensionless).
+/// * `transmissivity`: The transmissivity of the aquifer (in ft²/day).
+/// * `days_per_month`: The average number of days per month used in calculations.
+/// * `total_months`: The total number of months to calculate depletion for.
+///
+/// # Returns
+///
+/// A vector of tuples, where each tuple contains:
+/// * A `NaiveDate` representing the start of a month.
+/// * A `f64` value representing the streamflow depletion for that month (in acre-ft/month).
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] if the pumping, parameters or horizon are invalid; see
+/// [`calculate_streamflow_depletion`].
+pub fn calculate_streamflow_depletion_valley(
+    pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in acre-ft / month
+    geometry: &ParallelBoundaryGeometry,
+    specific_yield: f64,
+    transmissivity: f64,
+    days_per_month: f64,
+    total_months: usize,
+) -> Result<Vec<(NaiveDate, f64)>, DepletionError> {
+    let model = GloverAlluvial::from_geometry(geometry, specific_yield, transmissivity);
+    calculate_streamflow_depletion(
+        &model,
+        pumping_volumes_monthly,
+        days_per_month,
+        total_months,
+    )
+}
+
+/// The Glover solution for an alluvial aquifer bounded by a no-flow or constant-head boundary behind the well.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GloverAlluvial {
+    /// Distance from the well to the stream (in length units, typically feet).
+    pub distance_to_well: f64,
+    /// Distance from the stream to the boundary behind the well (in length units, typically feet).
+    pub distance_to_boundary: f64,
+    /// Storativity or specific yield of the aquifer (dimensionless).
+    pub specific_yield: f64,
+    /// Transmissivity of the aquifer along the major principal direction (in length²/time units, typically ft²/day).
+    pub transmissivity: f64,
+    /// The type of the boundary behind the well.
+    pub boundary_type: BoundaryType,
+    /// Horizontal anisotropy of the transmissivity, isotropic by default.
+    pub anisotropy: Anisotropy,
+}
+
+/// The hydraulic type of the far boundary of an alluvial aquifer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoundaryType {
+    /// An impermeable boundary such as a valley wall.
+    #[default]
+    NoFlow,
+    /// A constant-head boundary such as a second river or a canal, which captures part of the pumping.
+    ConstantHead,
+}
+
+impl GloverAlluvial {
+    pub fn new(
+        distance_to_well: f64,
+        distance_to_boundary: f64,
+        specific_yield: f64,
+        transmissivity: f64,
+    ) -> Self {
+        GloverAlluvial {
             distance_to_well,
             distance_to_boundary,
             specific_yield,
             transmissivity,
-            m as f64,
+            boundary_type: BoundaryType::NoFlow,
+            anisotropy: Anisotropy::default(),
+        }
+    }
+
+    /// Creates the alluvial model from validated aquifer parameters.
+    ///
+    /// # Parameters
+    ///
+    /// * `distance_to_well`: Distance from the well to the stream.
+    /// * `distance_to_boundary`: Distance from the stream to the boundary.
+    /// * `aquifer`: The validated storage and transmissivity of the aquifer.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DepletionError`] if a distance is negative or the well is beyond the boundary.
+    pub fn from_aquifer(
+        distance_to_well: Length,
+        distance_to_boundary: Length,
+        aquifer: &AquiferParams,
+    ) -> Result<Self, DepletionError> {
+        let model = GloverAlluvial::new(
+            distance_to_well.value(LengthUnit::Feet),
+            distance_to_boundary.value(LengthUnit::Feet),
+            aquifer.specific_yield(),
+            aquifer.transmissivity(),
         );
+        model.validate()?;
+        Ok(model)
     }
 
-    let pumping_rates_daily = monthly_pumping_to_daily(pumping_volumes_monthly);
+    /// Sets the horizontal anisotropy of the aquifer, with the principal direction measured from the stream.
+    pub fn with_anisotropy(mut self, anisotropy: Anisotropy) -> Self {
+        self.anisotropy = anisotropy;
+        self
+    }
 
-    // 3. Create a daily results Hashmap with daily time steps to hold the daily depletion amounts
-    let mut daily_depletion_amount = HashMap::new();
-    for (date, pumping_rate) in pumping_rates_daily {
-        if pumping_rate <= 0.0 {
-            continue;
-        }
-        let mut day_depletion = vec![0.0; total_days];
-        for base_depletion_index in 0..base_depletion_fraction.len() {
-            day_depletion[base_depletion_index] =
-                pumping_rate * base_depletion_fraction[base_depletion_index];
-        }
+    /// Sets the type of the boundary behind the well.
+    pub fn with_boundary_type(mut self, boundary_type: BoundaryType) -> Self {
+        self.boundary_type = boundary_type;
+        self
+    }
+
+    /// Returns the model of the depletion captured by the far boundary.
+    ///
+    /// A constant-head boundary captures part of the pumping, which is no longer taken from the
+    /// primary stream. A no-flow boundary captures nothing.
+    pub fn boundary_capture(&self) -> BoundaryCapture {
+        BoundaryCapture { aquifer: *self }
+    }
+
+    /// Creates the alluvial model for a well in a valley with parallel boundaries.
+    ///
+    /// The fully penetrating stream holds the head constant along its length, so the aquifer on
+    /// the far side of the stream is hydraulically separated from the well. Only the boundary on
+    /// the well's side of the stream contributes image wells.
+    ///
+    /// # Parameters
+    ///
+    /// * `geometry`: The positions of the stream, the valley walls and the side of the stream the well is on.
+    /// * `specific_yield`: Storativity of the aquifer (dimensionless).
+    /// * `transmissivity`: Transmissivity of the aquifer (in length²/time units, typically ft²/day).
+    pub fn from_geometry(
+        geometry: &ParallelBoundaryGeometry,
+        specific_yield: f64,
+        transmissivity: f64,
+    ) -> Self {
+        GloverAlluvial::new(
+            geometry.well_distance,
+            geometry.distance_to_boundary(),
+            specific_yield,
+            transmissivity,
+        )
+    }
+
+    /// Sums the response of the real well and its image wells at `time`.
+    ///
+    /// Each well contributes `response(u)` with the sign of its image, where `u` is the distance
+    /// from the image to the stream divided by the diffusion length `√(4Tt/S)`. Wells with
+    /// `u > cutoff` contribute nothing and end the series.
+    fn sum_image_wells<F: Fn(f64) -> f64>(&self, time: f64, cutoff: f64, response: F) -> f64 {
+        let mut total_depletion_fraction = 0.0;
+        let mut image_factor = 1.0;
+        let distance_to_well = self.anisotropy.transform_distance(self.distance_to_well);
+        let distance_to_boundary = self
+            .anisotropy
+            .transform_distance(self.distance_to_boundary);
+        let mut well_distance = -distance_to_well; // distance is negative to account for first loop
+        let diffusion_length = (4.0 * self.transmissivity * time / (self.specific_yield)).sqrt();
+
+        // Images reflected across a no-flow boundary keep their sign and alternate in pairs, while
+        // images reflected across a constant-head boundary change sign
+        let (boundary_sign, pair_sign) = match self.boundary_type {
+            BoundaryType::NoFlow => (1.0, -1.0),
+            BoundaryType::ConstantHead => (-1.0, 1.0),
+        };
+
+        loop {
+            // Real well or image well on the same side as the real well
+            well_distance += 2.0 * distance_to_well;
+            let u = well_distance / diffusion_length;
+            let depletion_fraction = if u > cutoff { 0.0 } else { response(u) };
+            total_depletion_fraction += depletion_fraction * image_factor;
+
+            if depletion_fraction == 0.0 {
+                break;
+            }
+
+            // Image well reflected across the boundary
+            well_distance = well_distance - 2.0 * distance_to_well + 2.0 * distance_to_boundary;
+            let u = well_distance / diffusion_length;
+            let depletion_fraction = if u > cutoff { 0.0 } else { response(u) };
+            total_depletion_fraction += depletion_fraction * image_factor * boundary_sign;
 
-        // add the day depletion to the daily depletion amount for the corresponding date and forward
-        for depletion_index in 0..day_depletion.len() {
-            let depletion_date = date + chrono::Duration::days(depletion_index as i64 + 1i64); // depletion is always the day after the pumping occurs
-            if depletion_index == 0 {
-                *daily_depletion_amount.entry(depletion_date).or_insert(0.0) +=
-                    day_depletion[depletion_index];
-                continue;
+            if depletion_fraction == 0.0 {
+                break;
             }
 
-            *daily_depletion_amount.entry(depletion_date).or_insert(0.0) +=
-                day_depletion[depletion_index] - day_depletion[depletion_index - 1];
+            image_factor *= pair_sign; // Sign for next pair of image wells
         }
-    }
 
-    // 4. sum the daily depletion amounts to monthly depletion totals and convert to acre-ft / month from ft³/month
-    let monthly_depletion_amount = create_monthly_depletion(&daily_depletion_amount);
-    let results = create_results_vector(
-        pumping_volumes_monthly,
-        total_months,
-        &monthly_depletion_amount,
-    );
+        total_depletion_fraction
+    }
+}
 
-    results
+/// The side of the stream a well is located on, looking across the valley.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamSide {
+    Left,
+    Right,
 }
 
-/// Aggregates daily depletion amounts into monthly totals and converts units.
-///
-/// This function takes daily streamflow depletion amounts and aggregates them into
-/// monthly totals. It also converts the units from cubic feet to acre-feet.
-///
-/// # Parameters
-///
-/// * `daily_depletion_amount`: A reference to a HashMap where keys are NaiveDates
-///   representing each day, and values are f64 representing the daily depletion
-///   amount in cubic feet.
-///
-/// # Returns
+/// A valley cross-section with a stream between two parallel impermeable boundaries.
 ///
-/// A new HashMap where keys are NaiveDates representing the first day of each month,
-/// and values are f64 representing the total monthly depletion amount in acre-feet.
-pub(crate) fn create_monthly_depletion(
-    daily_depletion_amount: &HashMap<NaiveDate, f64>,
-) -> HashMap<NaiveDate, f64> {
-    let mut monthly_depletion_amount = HashMap::new();
-    for (date, depletion_amount) in daily_depletion_amount {
-        let monthly_date = NaiveDate::from_ymd_opt(date.year(), date.month(), 1).unwrap(); // Monthly date
-        *monthly_depletion_amount.entry(monthly_date).or_insert(0.0) += depletion_amount / 43560f64; // Convert ft³ to acre-ft
-    }
-    monthly_depletion_amount
+/// Positions are measured along an axis perpendicular to the stream, increasing from the left
+/// boundary to the right boundary. A boundary can be set to `f64::NEG_INFINITY` or
+/// `f64::INFINITY` when the aquifer is unbounded on that side.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParallelBoundaryGeometry {
+    /// Position of the stream (in length units, typically feet).
+    pub stream_position: f64,
+    /// Position of the left valley wall (in length units, typically feet).
+    pub left_boundary: f64,
+    /// Position of the right valley wall (in length units, typically feet).
+    pub right_boundary: f64,
+    /// Distance from the well to the stream (in length units, typically feet).
+    pub well_distance: f64,
+    /// The side of the stream the well is located on.
+    pub well_side: StreamSide,
 }
 
-/// Creates a vector of monthly streamflow depletion results.
-///
-/// This function processes monthly depletion amounts and generates a vector of results,
-/// filtering out insignificant depletion values and handling complete aquifer depletion.
-///
-/// # Parameters
-///
-/// * `pumping_volumes_monthly`: A reference to a HashMap containing monthly pumping volumes,
-///   with NaiveDate keys and f64 values representing pumping volumes.
-/// * `total_months`: The total number of months to process.
-/// * `monthly_depletion_amount`: A mutable reference to a HashMap containing calculated monthly
-///   depletion amounts, with NaiveDate keys and f64 values representing depletion volumes.
-///
-/// # Returns
-///
-/// A Vec of tuples, where each tuple contains:
-/// * A NaiveDate representing the start of a month.
-/// * An f64 value representing the streamflow depletion for that month.
-///
-/// The vector only includes months when the depletion is greater than 0.001 units.
-/// The calculation stops if a negative depletion value is encountered, indicating complete aquifer depletion.
-pub(crate) fn create_results_vector(
-    pumping_volumes_monthly: &HashMap<NaiveDate, f64>,
-    total_months: usize,
-    monthly_depletion_amount: &HashMap<NaiveDate, f64>,
-) -> Vec<(NaiveDate, f64)> {
-    let mut results: Vec<(NaiveDate, f64)> = vec![];
-    // start date should be the oldest date key in the pumping_volumes_monthly HashMap
-    let start_date = pumping_volumes_monthly.keys().min().unwrap().clone();
-    results.reserve(total_months); // Reserve space for results to avoid reallocating
-    // let start_date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();  // should get from the input parameters
-    for month in 0..total_months {
-        let result_date = add_months(start_date, month as i32).unwrap(); // depletion is always the day after the pumping occurs
-        let monthly_depletion = *monthly_depletion_amount.get(&result_date).unwrap_or(&0.0);
-
-        if monthly_depletion < 0.0 {
-            // The depletion is negative, which means the aquifer has been depleted completely,
-            // so we should stop the simulation and return the results.
-            break;
+impl ParallelBoundaryGeometry {
+    pub fn new(
+        stream_position: f64,
+        left_boundary: f64,
+        right_boundary: f64,
+        well_distance: f64,
+        well_side: StreamSide,
+    ) -> Self {
+        ParallelBoundaryGeometry {
+            stream_position,
+            left_boundary,
+            right_boundary,
+            well_distance,
+            well_side,
         }
+    }
+
+    /// Returns the position of the well along the cross-valley axis.
+    pub fn well_position(&self) -> f64 {
+        match self.well_side {
+            StreamSide::Left => self.stream_position - self.well_distance,
+            StreamSide::Right => self.stream_position + self.well_distance,
+        }
+    }
 
-        if monthly_depletion > 0.001 {
-            results.push((result_date, monthly_depletion));
+    /// Returns the distance from the stream to the boundary on the well's side of the stream.
+    pub fn distance_to_boundary(&self) -> f64 {
+        match self.well_side {
+            StreamSide::Left => self.stream_position - self.left_boundary,
+            StreamSide::Right => self.right_boundary - self.stream_position,
         }
     }
-    results
 }
 
-/// Converts monthly pumping volumes to daily pumping rates.
-///
-/// This function takes a HashMap of monthly pumping volumes and converts them into
-/// daily pumping rates, distributing the monthly volume evenly across each day of the month.
-///
-/// # Parameters
-///
-/// * `pumping_volumes_monthly`: A reference to a HashMap where the keys are `NaiveDate`s
-///   representing the start of each month, and the values are `f64`s representing the
-///   pumping volume for that month in acre-feet.
-///
-/// # Returns
-///
-/// A new HashMap where the keys are `NaiveDate`s representing each day, and the values
-/// are `f64`s representing the daily pumping rate in cubic feet per day.
-///
-/// # Note
-///
-/// The function assumes that the input volumes are in acre-feet and converts them to
-/// cubic feet per day in the output.
-pub(crate) fn monthly_pumping_to_daily(
-    pumping_volumes_monthly: &HashMap<NaiveDate, f64>,
-) -> HashMap<NaiveDate, f64> {
-    // 2. convert pumping_volumes_monthly to pumping_rates_daily using the number of days in the month of the NaiveDate
-    let mut pumping_rates_daily = HashMap::new();
-    for (date, pumping_volume) in pumping_volumes_monthly {
-        let days_in_month = date.num_days_in_month();
-
-        // for each day in the month, calculate the daily pumping rate, and store it in pumping_rates_daily by NaiveDate and amount
-        for d in 0..days_in_month {
-            let date_daily =
-                NaiveDate::from_ymd_opt(date.year(), date.month(), (d + 1u8) as u32).unwrap();
-            let daily_pumping_rate = pumping_volume * 43_560f64 / (days_in_month as f64);
-            *pumping_rates_daily.entry(date_daily).or_insert(0.0) += daily_pumping_rate;
+impl DepletionModel for GloverAlluvial {
+    fn name(&self) -> &'static str {
+        match self.boundary_type {
+            BoundaryType::NoFlow => "Glover alluvial aquifer",
+            BoundaryType::ConstantHead => "Glover alluvial aquifer with constant-head boundary",
         }
     }
-    pumping_rates_daily
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("distance to well", self.distance_to_well),
+            ("distance to boundary", self.distance_to_boundary),
+            ("specific yield", self.specific_yield),
+            ("transmissivity", self.transmissivity),
+            ("anisotropy ratio", self.anisotropy.ratio),
+            ("principal direction", self.anisotropy.principal_direction),
+        ]
+    }
+
+    /// Returns the steady-state share of the pumping captured from the stream.
+    ///
+    /// Behind a no-flow boundary all of the pumping eventually comes from the stream. Between the
+    /// stream and a constant-head boundary the pumping is shared in inverse proportion to the
+    /// distance to each, unless the boundary is infinitely far away.
+    fn ultimate_fraction(&self) -> f64 {
+        match self.boundary_type {
+            BoundaryType::NoFlow => 1.0,
+            BoundaryType::ConstantHead if self.distance_to_boundary.is_infinite() => 1.0,
+            BoundaryType::ConstantHead => {
+                (self.distance_to_boundary - self.distance_to_well) / self.distance_to_boundary
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), DepletionError> {
+        check_non_negative("distance to well", self.distance_to_well)?;
+        // the image wells never die out if the boundary is on the stream, while an infinitely
+        // distant boundary leaves an unbounded aquifer with no image wells
+        check_non_negative("distance to boundary", self.distance_to_boundary)?;
+        if self.distance_to_boundary == 0.0 {
+            return Err(DepletionError::NonPhysicalParameter {
+                name: "distance to boundary",
+                value: self.distance_to_boundary,
+            });
+        }
+        if self.distance_to_well > self.distance_to_boundary {
+            return Err(DepletionError::InconsistentParameters {
+                name: "distance to well",
+                value: self.distance_to_well,
+                other: "distance to boundary",
+                other_value: self.distance_to_boundary,
+            });
+        }
+        AquiferParams::new(self.specific_yield, self.transmissivity)?;
+        self.anisotropy.validate()
+    }
+
+    /// Calculates the depletion fraction for streamflow depletion in an alluvial aquifer.
+    ///
+    /// This function computes the fraction of pumping that has been captured from the stream
+    /// at a given time, based on aquifer properties and the distance to the stream, specifically
+    /// for an alluvial aquifer scenario. It also includes the image wells that are determined by
+    /// the factor that is created.
+    ///
+    /// # Parameters
+    ///
+    /// * `time`: Time since pumping began (in time units, typically days).
+    ///
+    /// # Returns
+    ///
+    /// Returns the depletion fraction as a `f64`, representing the proportion of pumping
+    /// that has been captured from the stream at the given time in an alluvial aquifer setting.
+    fn depletion_fraction(&self, time: f64) -> f64 {
+        if time <= 0.0 {
+            return 0.0;
+        }
+        self.sum_image_wells(time, FRACTION_CUTOFF, erfc)
+    }
+
+    /// Calculates the depletion volume per unit pumping rate from the time integral of each image well.
+    ///
+    /// # Parameters
+    ///
+    /// * `time`: Time since pumping began (in time units, typically days).
+    fn depletion_volume(&self, time: f64) -> f64 {
+        if time <= 0.0 {
+            return 0.0;
+        }
+        time * self.sum_image_wells(time, VOLUME_CUTOFF, erfc_integral)
+    }
+
+    fn volume_response(&self, total_days: usize) -> Vec<f64> {
+        (0..total_days)
+            .map(|m| self.depletion_volume(m as f64))
+            .collect()
+    }
 }
 
-/// Calculates the depletion fraction for streamflow depletion in an alluvial aquifer.
-///
-/// This function computes the fraction of pumping that has been captured from the stream
-/// at a given time, based on aquifer properties and the distance to the stream, specifically
-/// for an alluvial aquifer scenario. It also includes the image wells that are determined by
-/// the factor that is created.
-///
-/// # Parameters
-///
-/// * `distance_to_well`: Distance from the well to the stream (in length units, typically feet).
-/// * `distance_to_boundary`: Distance from the well to boundary (in length units, typically feet).
-/// * `specific_yield`: Storativity of the aquifer (dimensionless).
-/// * `transmissivity`: Transmissivity of the aquifer (in length²/time units, typically ft²/day).
-/// * `time`: Time since pumping began (in time units, typically days).
-///
-/// # Returns
-///
-/// Returns the depletion fraction as a `f64`, representing the proportion of pumping
-/// that has been captured from the stream at the given time in an alluvial aquifer setting.
-fn calculate_depletion_fraction_alluvial_aquifer(
-    distance_to_well: f64,
-    distance_to_boundary: f64,
-    specific_yield: f64,
-    transmissivity: f64,
-    time: f64,
-) -> f64 {
-    let mut total_depletion_fraction = 0.0;
-    let mut image_factor = 1.0;
-    let mut well_distance = -distance_to_well; // distance is negative to account for first loop
-
-    loop {
-        // Real well or positive image well
-        well_distance += 2.0 * distance_to_well;
-        let u = well_distance / (4.0 * transmissivity * time / (specific_yield)).sqrt();
-        let depletion_fraction = if u > 2.9 { 0.0 } else { erfc(u) };
-        total_depletion_fraction += depletion_fraction * image_factor;
-
-        if depletion_fraction == 0.0 {
-            break;
+/// The depletion captured by the far boundary of a [`GloverAlluvial`] aquifer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundaryCapture {
+    aquifer: GloverAlluvial,
+}
+
+impl BoundaryCapture {
+    /// Returns the alluvial model seen from a constant-head boundary, or `None` for a no-flow
+    /// boundary or an infinitely distant boundary, which capture nothing.
+    fn boundary_aquifer(&self) -> Option<GloverAlluvial> {
+        match self.aquifer.boundary_type {
+            BoundaryType::NoFlow => None,
+            BoundaryType::ConstantHead if self.aquifer.distance_to_boundary.is_infinite() => None,
+            BoundaryType::ConstantHead => Some(
+                GloverAlluvial::new(
+                    self.aquifer.distance_to_boundary - self.aquifer.distance_to_well,
+                    self.aquifer.distance_to_boundary,
+                    self.aquifer.specific_yield,
+                    self.aquifer.transmissivity,
+                )
+                .with_boundary_type(BoundaryType::ConstantHead)
+                .with_anisotropy(self.aquifer.anisotropy),
+            ),
         }
+    }
+}
+
+impl DepletionModel for BoundaryCapture {
+    fn name(&self) -> &'static str {
+        "Alluvial boundary capture"
+    }
 
-        // Negative image well
-        well_distance = well_distance - 2.0 * distance_to_well + 2.0 * distance_to_boundary;
-        let u = well_distance / (4.0 * transmissivity * time / (specific_yield)).sqrt();
-        let depletion_fraction = if u > 2.9 { 0.0 } else { erfc(u) };
-        total_depletion_fraction += depletion_fraction * image_factor;
+    /// Returns the parameters of the aquifer, with a constant-head boundary reported as 1 and a
+    /// no-flow boundary as 0.
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        let constant_head = match self.aquifer.boundary_type {
+            BoundaryType::NoFlow => 0.0,
+            BoundaryType::ConstantHead => 1.0,
+        };
+        let mut parameters = self.aquifer.parameters();
+        parameters.push(("constant-head boundary", constant_head));
+        parameters
+    }
 
-        if depletion_fraction == 0.0 {
-            break;
+    fn ultimate_fraction(&self) -> f64 {
+        match self.aquifer.boundary_type {
+            BoundaryType::NoFlow => 0.0,
+            BoundaryType::ConstantHead => 1.0 - self.aquifer.ultimate_fraction(),
         }
+    }
+
+    fn validate(&self) -> Result<(), DepletionError> {
+        self.aquifer.validate()
+    }
 
-        image_factor *= -1.0; // Alternate sign for next pair of image wells
+    /// Calculates the fraction of pumping captured by the far boundary.
+    ///
+    /// For a constant-head boundary this is the same image-well series seen from the boundary,
+    /// with the stream acting as the constant-head boundary behind the well.
+    ///
+    /// # Parameters
+    ///
+    /// * `time`: Time since pumping began (in time units, typically days).
+    fn depletion_fraction(&self, time: f64) -> f64 {
+        self.boundary_aquifer()
+            .map_or(0.0, |aquifer| aquifer.depletion_fraction(time))
     }
 
-    total_depletion_fraction
+    fn depletion_volume(&self, time: f64) -> f64 {
+        self.boundary_aquifer()
+            .map_or(0.0, |aquifer| aquifer.depletion_volume(time))
+    }
+
+    fn volume_response(&self, total_days: usize) -> Vec<f64> {
+        self.boundary_aquifer().map_or_else(
+            || vec![0.0; total_days],
+            |aquifer| aquifer.volume_response(total_days),
+        )
+    }
+}
+
+/// Monthly depletion of an alluvial aquifer split between the primary stream and the far boundary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlluvialDepletion {
+    /// Monthly depletion of the primary stream in acre-ft/month.
+    pub stream: Vec<(NaiveDate, f64)>,
+    /// Monthly depletion captured by the far boundary in acre-ft/month.
+    pub boundary: Vec<(NaiveDate, f64)>,
+}
+
+/// Calculates alluvial depletion split between the primary stream and the far boundary.
+///
+/// # Parameters
+///
+/// * `model`: The alluvial aquifer, including the type of the far boundary.
+/// * `pumping_volumes_monthly`: A HashMap containing monthly pumping volumes (in acre-ft/month)
+///   indexed by their corresponding dates.
+/// * `days_per_month`: The average number of days per month used in calculations.
+/// * `total_months`: The total number of months to calculate depletion for.
+///
+/// # Returns
+///
+/// An [`AlluvialDepletion`] with the monthly depletion of the stream and of the boundary.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] if the pumping, parameters or horizon are invalid; see
+/// [`calculate_streamflow_depletion`].
+pub fn calculate_alluvial_depletion_split(
+    model: &GloverAlluvial,
+    pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in acre-ft / month
+    days_per_month: f64,
+    total_months: usize,
+) -> Result<AlluvialDepletion, DepletionError> {
+    Ok(AlluvialDepletion {
+        stream: calculate_streamflow_depletion(
+            model,
+            pumping_volumes_monthly,
+            days_per_month,
+            total_months,
+        )?,
+        boundary: calculate_streamflow_depletion(
+            &model.boundary_capture(),
+            pumping_volumes_monthly,
+            days_per_month,
+            total_months,
+        )?,
+    })
 }
 
 #[cfg(test)]
@@ -291,16 +595,17 @@ mod tests {
             converted_t,
             days_per_month,
             total_months,
-        );
+        )
+        .unwrap();
         // println!("Monthly depletion amounts");
         // for month in 0..value.len() {
         //     println!("{}: {}", value[month].0, value[month].1);
         // }
-        
+
         assert!(value.len() <= total_months); // Test if results vector has correct length
 
         let tolerance = 0.00001; // 10^-5 for 5 decimal places
-        
+
         // values that should be checked are:
         // 2025-01-01: 8.171540778208811
         // 2025-02-01: 21.22159873127743
@@ -315,4 +620,239 @@ mod tests {
         assert!((round_to_5_decimals(value[4].1) - 8.55314).abs() < tolerance);
         assert!((round_to_5_decimals(value[5].1) - 6.69913).abs() < tolerance);
     }
+
+    #[test]
+    fn test_valley_geometry_matches_alluvial() {
+        let s: f64 = 0.2;
+        let t: f64 = 261_800.0 / 7.481;
+
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0);
+        let days_per_month = 30.42;
+        let total_months = 120;
+
+        // Stream at 5000 ft in a valley from 0 to 13000 ft, well 4000 ft from the stream on the right
+        let right = ParallelBoundaryGeometry::new(5000.0, 0.0, 13_000.0, 4000.0, StreamSide::Right);
+        assert_eq!(right.well_position(), 9000.0);
+        assert_eq!(right.distance_to_boundary(), 8000.0);
+
+        let value = calculate_streamflow_depletion_valley(
+            &pumping_volumes,
+            &right,
+            s,
+            t,
+            days_per_month,
+            total_months,
+        )
+        .unwrap();
+        let expected = calculate_streamflow_depletion_alluvial(
+            &pumping_volumes,
+            4000.0,
+            8000.0,
+            s,
+            t,
+            days_per_month,
+            total_months,
+        )
+        .unwrap();
+        assert_eq!(value.len(), expected.len());
+        for (value, expected) in value.iter().zip(expected.iter()) {
+            assert!((value.1 - expected.1).abs() < 1e-9);
+        }
+
+        // The same well mirrored onto the far bank sees the same boundary distance
+        let left = ParallelBoundaryGeometry::new(8000.0, 0.0, 20_000.0, 4000.0, StreamSide::Left);
+        let left_model = GloverAlluvial::from_geometry(&left, s, t);
+        let right_model = GloverAlluvial::from_geometry(&right, s, t);
+        for time in [30.0, 365.0, 3650.0] {
+            assert_eq!(
+                left_model.depletion_fraction(time),
+                right_model.depletion_fraction(time)
+            );
+        }
+    }
+
+    #[test]
+    fn test_unbounded_side_matches_infinite_aquifer() {
+        let s: f64 = 0.2;
+        let t: f64 = 261_800.0 / 7.481;
+        let geometry =
+            ParallelBoundaryGeometry::new(0.0, -6000.0, f64::INFINITY, 4000.0, StreamSide::Right);
+        let model = GloverAlluvial::from_geometry(&geometry, s, t);
+        assert_eq!(model.validate(), Ok(()));
+
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0);
+        let value =
+            calculate_streamflow_depletion_valley(&pumping_volumes, &geometry, s, t, 30.42, 120)
+                .unwrap();
+        let expected = crate::calculate_streamflow_depletion_infinite(
+            &pumping_volumes,
+            4000.0,
+            s,
+            t,
+            30.42,
+            120,
+        )
+        .unwrap();
+        // image wells are dropped once erfc(u) falls below erfc(2.9), about 4e-5
+        assert_eq!(value.len(), expected.len());
+        for (value, expected) in value.iter().zip(expected.iter()) {
+            assert!((value.1 - expected.1).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_constant_head_boundary_capture() {
+        let d: f64 = 4000.0;
+        let b: f64 = 8000.0;
+        let s: f64 = 0.2;
+        let t: f64 = 261_800.0 / 7.481;
+        let no_flow = GloverAlluvial::new(d, b, s, t);
+        let constant_head = no_flow.with_boundary_type(BoundaryType::ConstantHead);
+
+        // the boundary takes water that would otherwise come from the stream
+        assert!(constant_head.depletion_fraction(365.0) < no_flow.depletion_fraction(365.0));
+        assert_eq!(no_flow.boundary_capture().depletion_fraction(365.0), 0.0);
+
+        // at steady state the pumping is split in proportion to the distances
+        let time = 1.0e6;
+        let stream = constant_head.depletion_fraction(time);
+        let boundary = constant_head.boundary_capture().depletion_fraction(time);
+        assert!((stream - (b - d) / b).abs() < 1e-3);
+        assert!((boundary - d / b).abs() < 1e-3);
+        assert_eq!(constant_head.ultimate_fraction(), 0.5);
+        assert_eq!(constant_head.boundary_capture().ultimate_fraction(), 0.5);
+
+        // an infinitely distant boundary captures nothing
+        let unbounded = GloverAlluvial::new(d, f64::INFINITY, s, t)
+            .with_boundary_type(BoundaryType::ConstantHead);
+        assert_eq!(unbounded.ultimate_fraction(), 1.0);
+        assert_eq!(unbounded.boundary_capture().ultimate_fraction(), 0.0);
+        assert_eq!(unbounded.boundary_capture().depletion_fraction(365.0), 0.0);
+        assert_eq!(unbounded.boundary_capture().depletion_volume(365.0), 0.0);
+        let glover = crate::GloverInfinite::new(d, s, t);
+        assert!(
+            (unbounded.depletion_fraction(365.0) - glover.depletion_fraction(365.0)).abs() < 1e-4
+        );
+
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0);
+        let split =
+            calculate_alluvial_depletion_split(&unbounded, &pumping_volumes, 30.42, 120).unwrap();
+        assert!(split.boundary.is_empty());
+        assert!(split.stream.iter().all(|(_, value)| value.is_finite()));
+        assert!(split.stream.iter().map(|(_, v)| v).sum::<f64>() > 90.0);
+
+        assert_eq!(
+            constant_head.boundary_capture().parameters().last(),
+            Some(&("constant-head boundary", 1.0))
+        );
+        assert_eq!(
+            no_flow.boundary_capture().parameters().last(),
+            Some(&("constant-head boundary", 0.0))
+        );
+        let split =
+            calculate_alluvial_depletion_split(&constant_head, &pumping_volumes, 30.42, 600)
+                .unwrap();
+        let stream_total: f64 = split.stream.iter().map(|(_, v)| v).sum();
+        let boundary_total: f64 = split.boundary.iter().map(|(_, v)| v).sum();
+        assert!(boundary_total > 0.0);
+        assert!((stream_total + boundary_total - 100.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_anisotropic_alluvial_aquifer() {
+        let s: f64 = 0.2;
+        let t: f64 = 261_800.0 / 7.481;
+        let isotropic = GloverAlluvial::new(4000.0, 8000.0, s, t);
+        let unit_ratio = isotropic.with_anisotropy(Anisotropy::new(1.0, 1.2));
+        let along = isotropic.with_anisotropy(Anisotropy::new(0.5, 0.0));
+        let minor = GloverAlluvial::new(4000.0, 8000.0, s, 0.5 * t);
+
+        for time in [30.0, 365.0, 3650.0] {
+            assert_eq!(
+                unit_ratio.depletion_fraction(time),
+                isotropic.depletion_fraction(time)
+            );
+            assert!((along.depletion_fraction(time) - minor.depletion_fraction(time)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_well_beyond_boundary_is_rejected() {
+        let aquifer = AquiferParams::new(0.2, 261_800.0 / 7.481).unwrap();
+        assert!(
+            GloverAlluvial::from_aquifer(Length::feet(4000.0), Length::feet(8000.0), &aquifer)
+                .is_ok()
+        );
+        assert_eq!(
+            GloverAlluvial::from_aquifer(Length::feet(9000.0), Length::feet(8000.0), &aquifer),
+            Err(DepletionError::InconsistentParameters {
+                name: "distance to well",
+                value: 9000.0,
+                other: "distance to boundary",
+                other_value: 8000.0
+            })
+        );
+
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0);
+        let value = calculate_streamflow_depletion_alluvial(
+            &pumping_volumes,
+            9000.0,
+            8000.0,
+            0.2,
+            261_800.0 / 7.481,
+            30.42,
+            12,
+        );
+        assert!(matches!(
+            value,
+            Err(DepletionError::InconsistentParameters { .. })
+        ));
+
+        // a boundary on the stream leaves no aquifer
+        assert_eq!(
+            GloverAlluvial::new(0.0, 0.0, 0.2, 261_800.0 / 7.481).validate(),
+            Err(DepletionError::NonPhysicalParameter {
+                name: "distance to boundary",
+                value: 0.0
+            })
+        );
+        assert!(
+            GloverAlluvial::new(0.0, f64::NAN, 0.2, 261_800.0 / 7.481)
+                .validate()
+                .is_err()
+        );
+        let on_stream = GloverAlluvial::new(0.0, 8000.0, 0.2, 261_800.0 / 7.481);
+        assert_eq!(on_stream.validate(), Ok(()));
+        assert_eq!(on_stream.depletion_fraction(0.0), 0.0);
+
+        // an unbounded aquifer is accepted and matches the infinite aquifer
+        let unbounded = calculate_streamflow_depletion_alluvial(
+            &pumping_volumes,
+            4000.0,
+            f64::INFINITY,
+            0.2,
+            261_800.0 / 7.481,
+            30.42,
+            12,
+        )
+        .unwrap();
+        let infinite = crate::calculate_streamflow_depletion_infinite(
+            &pumping_volumes,
+            4000.0,
+            0.2,
+            261_800.0 / 7.481,
+            30.42,
+            12,
+        )
+        .unwrap();
+        // the image series drops a well once erfc(u) falls below erfc(2.9)
+        assert_eq!(unbounded.len(), infinite.len());
+        for (value, expected) in unbounded.iter().zip(infinite.iter()) {
+            assert!((value.1 - expected.1).abs() < 1e-4);
+        }
+    }
 }
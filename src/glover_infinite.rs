@@ -1,6 +1,10 @@
-use crate::glover_alluvial::{
-    create_monthly_depletion, create_results_vector, monthly_pumping_to_daily,
-};
+use crate::anisotropy::Anisotropy;
+use crate::engine::calculate_streamflow_depletion;
+use crate::error::{DepletionError, check_non_negative};
+use crate::model::DepletionModel;
+use crate::params::AquiferParams;
+use crate::units::{Length, LengthUnit};
+use crate::utils::{erfc_integral, simpson};
 use chrono::NaiveDate;
 use scirs2_special::erfc;
 use std::collections::HashMap;
@@ -22,6 +26,11 @@ use std::collections::HashMap;
 /// # Returns
 ///
 /// A Vec of tuples, where each tuple contains a date and the corresponding monthly streamflow depletion in acre-ft/month.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] if the pumping, parameters or horizon are invalid; see
+/// [`calculate_streamflow_depletion`].
 pub fn calculate_streamflow_depletion_infinite(
     pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in acre-ft / month
     distance_to_well: f64,
@@ -29,80 +38,189 @@ pub fn calculate_streamflow_depletion_infinite(
     transmissivity: f64,
     days_per_month: f64,
     total_months: usize,
-) -> Vec<(NaiveDate, f64)> {
-    // get total days
-    let total_days = (total_months as f64 * days_per_month).ceil() as usize;
-
-    // 1. calculate the depletion fraction for each time step
-    let mut base_depletion_fraction = vec![0.0; total_days];
-    for m in 0..total_days {
-        base_depletion_fraction[m] = calculate_depletion_fraction(
+) -> Result<Vec<(NaiveDate, f64)>, DepletionError> {
+    let model = GloverInfinite::new(distance_to_well, specific_yield, transmissivity);
+    calculate_streamflow_depletion(
+        &model,
+        pumping_volumes_monthly,
+        days_per_month,
+        total_months,
+    )
+}
+
+/// The Glover solution for a well near a fully penetrating stream in an infinite aquifer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GloverInfinite {
+    /// Distance from the well to the stream (in length units, typically feet).
+    pub distance_to_well: f64,
+    /// Storativity or specific yield of the aquifer (dimensionless).
+    pub specific_yield: f64,
+    /// Transmissivity of the aquifer along the major principal direction (in length²/time units, typically ft²/day).
+    pub transmissivity: f64,
+    /// Horizontal anisotropy of the transmissivity, isotropic by default.
+    pub anisotropy: Anisotropy,
+}
+
+impl GloverInfinite {
+    pub fn new(distance_to_well: f64, specific_yield: f64, transmissivity: f64) -> Self {
+        GloverInfinite {
             distance_to_well,
             specific_yield,
             transmissivity,
-            m as f64,
-        );
+            anisotropy: Anisotropy::default(),
+        }
     }
 
-    let pumping_rates_daily = monthly_pumping_to_daily(pumping_volumes_monthly);
+    /// Creates the Glover model from validated aquifer parameters.
+    ///
+    /// # Parameters
+    ///
+    /// * `distance_to_well`: Distance from the well to the stream.
+    /// * `aquifer`: The validated storage and transmissivity of the aquifer.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DepletionError`] if the distance is negative.
+    pub fn from_aquifer(
+        distance_to_well: Length,
+        aquifer: &AquiferParams,
+    ) -> Result<Self, DepletionError> {
+        let distance_to_well = distance_to_well.value(LengthUnit::Feet);
+        check_non_negative("distance to well", distance_to_well)?;
+        Ok(GloverInfinite::new(
+            distance_to_well,
+            aquifer.specific_yield(),
+            aquifer.transmissivity(),
+        ))
+    }
 
-    // 3. Create a daily results Hashmap with daily time steps to hold the daily depletion amounts
-    let mut daily_depletion_amount = HashMap::new();
-    for (date, pumping_rate) in pumping_rates_daily {
-        if pumping_rate <= 0.0 {
-            continue;
-        }
-        let mut day_depletion = vec![0.0; total_days];
-        for base_depletion_index in 0..base_depletion_fraction.len() {
-            day_depletion[base_depletion_index] =
-                pumping_rate * base_depletion_fraction[base_depletion_index];
-        }
+    /// Sets the horizontal anisotropy of the aquifer.
+    pub fn with_anisotropy(mut self, anisotropy: Anisotropy) -> Self {
+        self.anisotropy = anisotropy;
+        self
+    }
 
-        // add the day depletion to the daily depletion amount for the corresponding date and forward
-        for depletion_index in 0..day_depletion.len() {
-            let depletion_date = date + chrono::Duration::days(depletion_index as i64 + 1i64); // depletion is always the day after the pumping occurs
-            if depletion_index == 0 {
-                *daily_depletion_amount.entry(depletion_date).or_insert(0.0) +=
-                    day_depletion[depletion_index];
-                continue;
-            }
-
-            *daily_depletion_amount.entry(depletion_date).or_insert(0.0) +=
-                day_depletion[depletion_index] - day_depletion[depletion_index - 1];
+    /// Calculates the argument of the complementary error function, `√(S d² / (4 T t))`.
+    fn erfc_argument(&self, time: f64) -> f64 {
+        let distance_to_well = self.anisotropy.transform_distance(self.distance_to_well);
+        ((self.specific_yield * distance_to_well.powi(2)) / (4.0 * self.transmissivity * time))
+            .sqrt()
+    }
+}
+
+impl DepletionModel for GloverInfinite {
+    fn name(&self) -> &'static str {
+        "Glover infinite aquifer"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("distance to well", self.distance_to_well),
+            ("specific yield", self.specific_yield),
+            ("transmissivity", self.transmissivity),
+            ("anisotropy ratio", self.anisotropy.ratio),
+            ("principal direction", self.anisotropy.principal_direction),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), DepletionError> {
+        check_non_negative("distance to well", self.distance_to_well)?;
+        AquiferParams::new(self.specific_yield, self.transmissivity)?;
+        self.anisotropy.validate()
+    }
+
+    /// Calculates the depletion fraction for streamflow depletion using the Glover solution.
+    ///
+    /// This function computes the fraction of pumping that has been captured from the stream
+    /// at a given time, based on aquifer properties and the distance to the stream.
+    ///
+    /// # Parameters
+    ///
+    /// * `time`: Time since pumping began (in time units, typically days).
+    ///
+    /// # Returns
+    ///
+    /// Returns the depletion fraction as a `f64`, representing the proportion of pumping
+    /// that has been captured from the stream at the given time.
+    fn depletion_fraction(&self, time: f64) -> f64 {
+        // a well on the stream would give 0/0 at the start of pumping
+        if time <= 0.0 {
+            return 0.0;
         }
+        // Calculate erfc(z)
+        erfc(self.erfc_argument(time))
     }
 
-    let monthly_depletion_amount = create_monthly_depletion(&daily_depletion_amount);
-    let results = create_results_vector(
-        pumping_volumes_monthly,
-        total_months,
-        &monthly_depletion_amount,
-    );
+    /// Calculates the depletion volume per unit pumping rate from the closed-form time integral of the Glover solution.
+    ///
+    /// # Parameters
+    ///
+    /// * `time`: Time since pumping began (in time units, typically days).
+    ///
+    /// # Returns
+    ///
+    /// Returns `t [(1 + 2z²) erfc(z) - 2z exp(-z²) / √π]`, the integral of the depletion fraction from 0 to `time`.
+    fn depletion_volume(&self, time: f64) -> f64 {
+        if time <= 0.0 {
+            return 0.0;
+        }
+        time * erfc_integral(self.erfc_argument(time))
+    }
 
-    results
+    fn volume_response(&self, total_days: usize) -> Vec<f64> {
+        (0..total_days)
+            .map(|m| self.depletion_volume(m as f64))
+            .collect()
+    }
 }
 
-/// Calculates the depletion fraction for streamflow depletion using the Glover solution.
+/// Calculates the fraction of pumping captured from one reach of a straight stream.
 ///
-/// This function computes the fraction of pumping that has been captured from the stream
-/// at a given time, based on aquifer properties and the distance to the stream.
+/// The Glover solution is the flow from a well and its negative image across the stream line. The
+/// flow into the stream per unit length, integrated along the stream from `reach_start` to
+/// `reach_end`, gives the depletion of that reach. With the substitution `tan θ = y / d` the
+/// integral becomes `(1/π) ∫ exp(-S d² / (4 T t cos² θ)) dθ`, which is evaluated numerically.
+/// Over the whole stream this is Craig's form of `erfc`, so an unbounded reach reproduces the
+/// Glover solution.
 ///
 /// # Parameters
 ///
-/// * `d`: Distance from the well to the stream (in length units, typically feet).
-/// * `s`: Storativity of the aquifer (dimensionless).
-/// * `t`: Transmissivity of the aquifer (in length²/time units, typically ft²/day).
+/// * `distance_to_well`: Distance from the well to the stream line (in length units, typically feet).
+/// * `reach_start`: Start of the reach, measured along the stream from the point nearest the well.
+///   Can be `f64::NEG_INFINITY`.
+/// * `reach_end`: End of the reach, measured the same way. Can be `f64::INFINITY`.
+/// * `specific_yield`: Storativity of the aquifer (dimensionless).
+/// * `transmissivity`: Transmissivity of the aquifer (in length²/time units, typically ft²/day).
 /// * `time`: Time since pumping began (in time units, typically days).
 ///
 /// # Returns
 ///
-/// Returns the depletion fraction as a `f64`, representing the proportion of pumping
-/// that has been captured from the stream at the given time.
-fn calculate_depletion_fraction(d: f64, s: f64, t: f64, time: f64) -> f64 {
-    // Calculate the argument of the complementary error function
-    let z = ((s * d.powi(2)) / (4.0 * t * time)).sqrt();
-    // Calculate erfc(z)
-    erfc(z)
+/// Returns the proportion of pumping that has been captured from the reach at the given time.
+pub(crate) fn stream_reach_depletion_fraction(
+    distance_to_well: f64,
+    reach_start: f64,
+    reach_end: f64,
+    specific_yield: f64,
+    transmissivity: f64,
+    time: f64,
+) -> f64 {
+    if time <= 0.0 || reach_end <= reach_start {
+        return 0.0;
+    }
+    let h = specific_yield * distance_to_well.powi(2) / (4.0 * transmissivity * time);
+    let integrand = |theta: f64| (-h / theta.cos().powi(2)).exp();
+    let theta_start = (reach_start / distance_to_well).atan();
+    let theta_end = (reach_end / distance_to_well).atan();
+
+    // split at the point nearest the well, where the integrand peaks
+    let mut integral = 0.0;
+    if theta_start < 0.0 {
+        integral += simpson(integrand, theta_start, theta_end.min(0.0), 256);
+    }
+    if theta_end > 0.0 {
+        integral += simpson(integrand, theta_start.max(0.0), theta_end, 256);
+    }
+    integral / std::f64::consts::PI
 }
 
 #[cfg(test)]
@@ -135,7 +253,8 @@ mod tests {
             converted_t,
             days_per_month,
             total_months,
-        );
+        )
+        .unwrap();
         // println!("Monthly depletion amounts");
         // for month in 0..value.len() {
         //     println!("{}: {}", value[month].0, value[month].1);
@@ -144,7 +263,7 @@ mod tests {
         assert!(value.len() <= total_months); // Test if results vector has correct length
 
         let tolerance = 0.00001; // 10^-5 for 5 decimal places
-        
+
         // values that should be checked are:
         // 2025-01-01: 8.169915278703847
         // 2025-02-01: 20.979264088137487
@@ -159,4 +278,74 @@ mod tests {
         assert!((round_to_5_decimals(value[4].1) - 5.43355).abs() < tolerance);
         assert!((round_to_5_decimals(value[5].1) - 3.85735).abs() < tolerance);
     }
+
+    #[test]
+    fn test_stream_reach_depletion_fraction() {
+        let d: f64 = 4000.0;
+        let s: f64 = 0.2;
+        let t: f64 = 261_800.0 / 7.481;
+        let glover = GloverInfinite::new(d, s, t);
+
+        for time in [10.0, 30.0, 365.0, 3650.0] {
+            let whole =
+                stream_reach_depletion_fraction(d, f64::NEG_INFINITY, f64::INFINITY, s, t, time);
+            assert!((whole - glover.depletion_fraction(time)).abs() < 1e-6);
+
+            // each half of the stream takes half of the depletion
+            let half = stream_reach_depletion_fraction(d, 0.0, f64::INFINITY, s, t, time);
+            assert!((2.0 * half - whole).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_anisotropic_aquifer() {
+        let d: f64 = 4000.0;
+        let s: f64 = 0.2;
+        let t: f64 = 261_800.0 / 7.481;
+        let isotropic = GloverInfinite::new(d, s, t);
+
+        // A ratio of one reproduces the isotropic result exactly
+        let unit_ratio = isotropic.with_anisotropy(Anisotropy::new(1.0, 0.5));
+        // With the major axis along the stream only the minor transmissivity acts across it
+        let along = isotropic.with_anisotropy(Anisotropy::new(0.25, 0.0));
+        let minor = GloverInfinite::new(d, s, 0.25 * t);
+
+        for time in [30.0, 365.0, 3650.0] {
+            assert_eq!(
+                unit_ratio.depletion_fraction(time),
+                isotropic.depletion_fraction(time)
+            );
+            assert!(
+                (along.depletion_fraction(time) - minor.depletion_fraction(time)).abs() < 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_depletion_volume() {
+        let glover = GloverInfinite::new(4000.0, 0.2, 261_800.0 / 7.481);
+        assert_eq!(glover.depletion_volume(0.0), 0.0);
+        for time in [30.0, 365.0, 3650.0] {
+            let numeric = simpson(|t| glover.depletion_fraction(t), 0.0, time, 20_000);
+            assert!((glover.depletion_volume(time) - numeric).abs() < 1e-6 * time);
+        }
+
+        let response = glover.volume_response(400);
+        assert_eq!(response.len(), 400);
+        assert_eq!(response[365], glover.depletion_volume(365.0));
+    }
+
+    #[test]
+    fn test_well_on_stream() {
+        let glover = GloverInfinite::new(0.0, 0.2, 261_800.0 / 7.481);
+        assert_eq!(glover.validate(), Ok(()));
+        assert_eq!(glover.depletion_fraction(0.0), 0.0);
+        assert_eq!(glover.depletion_fraction(1.0), 1.0);
+        assert!(
+            glover
+                .unit_response(30)
+                .iter()
+                .all(|fraction| !fraction.is_nan())
+        );
+    }
 }
@@ -0,0 +1,313 @@
+use crate::engine::calculate_streamflow_depletion;
+use crate::error::{DepletionError, check_non_negative, check_positive};
+use crate::glover_infinite::GloverInfinite;
+use crate::model::DepletionModel;
+use crate::params::AquiferParams;
+use crate::units::{Length, LengthUnit};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+/// Calculates streamflow depletion for a semipervious streambed using the Hantush (1965) solution.
+///
+/// This function computes the monthly streamflow depletion based on given pumping volumes and aquifer parameters.
+/// The resistance of the streambed is represented by the streambed leakance, an additional effective distance
+/// that is added to the distance between the well and the stream in the Glover solution.
+///
+/// # Parameters
+///
+/// * `pumping_volumes_monthly`: A HashMap containing monthly pumping volumes in acre-ft/month, keyed by date.
+/// * `distance_to_well`: The distance from the well to the stream in feet.
+/// * `specific_yield`: The specific yield of the aquifer (dimensionless).
+/// * `transmissivity`: The transmissivity of the aquifer in ft²/day.
+/// * `streambed_leakance`: The streambed leakance in feet, see [`streambed_leakance`].
+/// * `days_per_month`: The average number of days per month used in calculations.
+/// * `total_months`: The total number of months to calculate depletion for.
+///
+/// # Returns
+///
+/// A Vec of tuples, where each tuple contains a date and the corresponding monthly streamflow depletion in acre-ft/month.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] if the pumping, parameters or horizon are invalid; see
+/// [`calculate_streamflow_depletion`].
+pub fn calculate_streamflow_depletion_hantush(
+    pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in acre-ft / month
+    distance_to_well: f64,
+    specific_yield: f64,
+    transmissivity: f64,
+    streambed_leakance: f64,
+    days_per_month: f64,
+    total_months: usize,
+) -> Result<Vec<(NaiveDate, f64)>, DepletionError> {
+    let model = Hantush1965::new(
+        distance_to_well,
+        specific_yield,
+        transmissivity,
+        streambed_leakance,
+    );
+    calculate_streamflow_depletion(
+        &model,
+        pumping_volumes_monthly,
+        days_per_month,
+        total_months,
+    )
+}
+
+/// Calculates the Hantush streambed leakance, `K * b' / K'`.
+///
+/// The leakance is the thickness of aquifer material that would offer the same resistance to flow
+/// as the streambed, and is added to the well to stream distance.
+///
+/// # Parameters
+///
+/// * `aquifer_conductivity`: The horizontal hydraulic conductivity of the aquifer in ft/day.
+/// * `streambed_thickness`: The thickness of the semipervious streambed in feet.
+/// * `streambed_conductivity`: The vertical hydraulic conductivity of the streambed in ft/day.
+///
+/// # Returns
+///
+/// The streambed leakance in feet.
+///
+/// # Errors
+///
+/// Returns [`DepletionError::NonPhysicalParameter`] if the streambed conductivity is not greater
+/// than zero or either other value is negative.
+pub fn streambed_leakance(
+    aquifer_conductivity: f64,
+    streambed_thickness: f64,
+    streambed_conductivity: f64,
+) -> Result<f64, DepletionError> {
+    check_non_negative("aquifer conductivity", aquifer_conductivity)?;
+    check_non_negative("streambed thickness", streambed_thickness)?;
+    check_positive("streambed conductivity", streambed_conductivity)?;
+    Ok(aquifer_conductivity * streambed_thickness / streambed_conductivity)
+}
+
+/// Monthly depletion with the streambed resistance and without it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreambedComparison {
+    /// Monthly depletion from the Hantush (1965) solution in acre-ft/month.
+    pub hantush: Vec<(NaiveDate, f64)>,
+    /// Monthly depletion from the Glover solution for the same well without the streambed in acre-ft/month.
+    pub glover: Vec<(NaiveDate, f64)>,
+}
+
+/// Calculates depletion from the Hantush (1965) solution and from Glover for the same pumping.
+///
+/// # Parameters
+///
+/// * `model`: The Hantush (1965) model; the Glover model is [`Hantush1965::without_streambed`].
+/// * `pumping_volumes_monthly`: A HashMap containing monthly pumping volumes (in acre-ft/month)
+///   indexed by their corresponding dates.
+/// * `days_per_month`: The average number of days per month used in calculations.
+/// * `total_months`: The total number of months to calculate depletion for.
+///
+/// # Returns
+///
+/// A [`StreambedComparison`] with the monthly depletion with and without the streambed.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] if the pumping, parameters or horizon are invalid; see
+/// [`calculate_streamflow_depletion`].
+pub fn calculate_streambed_comparison(
+    model: &Hantush1965,
+    pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in acre-ft / month
+    days_per_month: f64,
+    total_months: usize,
+) -> Result<StreambedComparison, DepletionError> {
+    Ok(StreambedComparison {
+        hantush: calculate_streamflow_depletion(
+            model,
+            pumping_volumes_monthly,
+            days_per_month,
+            total_months,
+        )?,
+        glover: calculate_streamflow_depletion(
+            &model.without_streambed(),
+            pumping_volumes_monthly,
+            days_per_month,
+            total_months,
+        )?,
+    })
+}
+
+/// The Hantush (1965) solution for a well near a stream with a semipervious streambed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hantush1965 {
+    /// Distance from the well to the stream (in length units, typically feet).
+    pub distance_to_well: f64,
+    /// Storativity or specific yield of the aquifer (dimensionless).
+    pub specific_yield: f64,
+    /// Transmissivity of the aquifer (in length²/time units, typically ft²/day).
+    pub transmissivity: f64,
+    /// Streambed leakance, the effective distance added for the streambed (in length units, typically feet).
+    pub streambed_leakance: f64,
+}
+
+impl Hantush1965 {
+    pub fn new(
+        distance_to_well: f64,
+        specific_yield: f64,
+        transmissivity: f64,
+        streambed_leakance: f64,
+    ) -> Self {
+        Hantush1965 {
+            distance_to_well,
+            specific_yield,
+            transmissivity,
+            streambed_leakance,
+        }
+    }
+
+    /// Creates the Hantush (1965) model from validated aquifer parameters.
+    ///
+    /// # Parameters
+    ///
+    /// * `distance_to_well`: Distance from the well to the stream.
+    /// * `aquifer`: The validated storage and transmissivity of the aquifer.
+    /// * `streambed_leakance`: Streambed leakance, the effective distance added for the streambed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DepletionError`] if the distance or the leakance is negative.
+    pub fn from_aquifer(
+        distance_to_well: Length,
+        aquifer: &AquiferParams,
+        streambed_leakance: Length,
+    ) -> Result<Self, DepletionError> {
+        let model = Hantush1965::new(
+            distance_to_well.value(LengthUnit::Feet),
+            aquifer.specific_yield(),
+            aquifer.transmissivity(),
+            streambed_leakance.value(LengthUnit::Feet),
+        );
+        model.validate()?;
+        Ok(model)
+    }
+
+    /// Returns the Glover model for the same well with the streambed resistance removed.
+    pub fn without_streambed(&self) -> GloverInfinite {
+        GloverInfinite::new(
+            self.distance_to_well,
+            self.specific_yield,
+            self.transmissivity,
+        )
+    }
+
+    /// Returns the Glover model at the effective distance that represents this solution.
+    fn effective_glover(&self) -> GloverInfinite {
+        GloverInfinite::new(
+            self.distance_to_well + self.streambed_leakance,
+            self.specific_yield,
+            self.transmissivity,
+        )
+    }
+}
+
+impl DepletionModel for Hantush1965 {
+    fn name(&self) -> &'static str {
+        "Hantush (1965) semipervious streambed"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("distance to well", self.distance_to_well),
+            ("specific yield", self.specific_yield),
+            ("transmissivity", self.transmissivity),
+            ("streambed leakance", self.streambed_leakance),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), DepletionError> {
+        check_non_negative("streambed leakance", self.streambed_leakance)?;
+        self.without_streambed().validate()
+    }
+
+    /// Calculates the depletion fraction using the Hantush (1965) effective distance.
+    ///
+    /// # Parameters
+    ///
+    /// * `time`: Time since pumping began (in time units, typically days).
+    ///
+    /// # Returns
+    ///
+    /// Returns the depletion fraction as a `f64`, representing the proportion of pumping
+    /// that has been captured from the stream at the given time.
+    fn depletion_fraction(&self, time: f64) -> f64 {
+        self.effective_glover().depletion_fraction(time)
+    }
+
+    fn depletion_volume(&self, time: f64) -> f64 {
+        self.effective_glover().depletion_volume(time)
+    }
+
+    fn volume_response(&self, total_days: usize) -> Vec<f64> {
+        self.effective_glover().volume_response(total_days)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hantush_compared_to_glover() {
+        // Aquifer parameters (in feet-based units)
+        let d: f64 = 4000.0; // Distance to stream (ft)
+        let s: f64 = 0.2; // Storativity (dimensionless)
+        let t: f64 = 261_800.0 / 7.481; // Transmissivity (ft²/day)
+        let leakance = streambed_leakance(350.0, 2.0, 1.0).unwrap(); // 700 ft
+
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0); // acre-feet for month 1
+        let days_per_month = 30.42; // Average days per month
+        let total_months = 120; // 10 years
+
+        let model = Hantush1965::new(d, s, t, leakance);
+        let StreambedComparison { hantush, glover } =
+            calculate_streambed_comparison(&model, &pumping_volumes, days_per_month, total_months)
+                .unwrap();
+
+        // The streambed delays depletion, so the first month is smaller than Glover
+        assert!(hantush[0].1 < glover[0].1);
+
+        // and it matches Glover with the effective distance
+        let effective = crate::calculate_streamflow_depletion_infinite(
+            &pumping_volumes,
+            d + leakance,
+            s,
+            t,
+            days_per_month,
+            total_months,
+        )
+        .unwrap();
+        assert_eq!(hantush.len(), effective.len());
+        for (hantush, effective) in hantush.iter().zip(effective.iter()) {
+            assert!((hantush.1 - effective.1).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_streambed_inputs_are_checked() {
+        assert_eq!(
+            streambed_leakance(350.0, 2.0, 0.0),
+            Err(DepletionError::NonPhysicalParameter {
+                name: "streambed conductivity",
+                value: 0.0
+            })
+        );
+        assert!(streambed_leakance(350.0, 2.0, -1.0).is_err());
+
+        let aquifer = AquiferParams::new(0.2, 35_000.0).unwrap();
+        let model =
+            Hantush1965::from_aquifer(Length::meters(1219.2), &aquifer, Length::meters(213.36))
+                .unwrap();
+        assert!((model.distance_to_well - 4000.0).abs() < 1e-9);
+        assert!((model.streambed_leakance - 700.0).abs() < 1e-9);
+        assert!(
+            Hantush1965::from_aquifer(Length::feet(4000.0), &aquifer, Length::feet(-1.0)).is_err()
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use crate::engine::calculate_streamflow_depletion;
+use crate::error::{DepletionError, check_non_negative, check_positive};
+use crate::model::DepletionModel;
+use crate::params::AquiferParams;
+use crate::units::{Conductivity, ConductivityUnit, Length, LengthUnit};
+use crate::utils::erfcx;
+use chrono::NaiveDate;
+use scirs2_special::erfc;
+use std::collections::HashMap;
+
+/// Calculates streamflow depletion for a partially clogged streambed using the Hunt (1999) solution.
+///
+/// This function computes the monthly streamflow depletion based on given pumping volumes and aquifer parameters.
+/// Unlike the Glover solution, the stream is separated from the aquifer by a streambed with finite
+/// conductance, which delays and reduces early depletion.
+///
+/// # Parameters
+///
+/// * `pumping_volumes_monthly`: A HashMap containing monthly pumping volumes in acre-ft/month, keyed by date.
+/// * `distance_to_well`: The distance from the well to the stream in feet.
+/// * `specific_yield`: The specific yield of the aquifer (dimensionless).
+/// * `transmissivity`: The transmissivity of the aquifer in ft²/day.
+/// * `streambed_conductance`: The streambed conductance (lambda) in ft/day.
+/// * `days_per_month`: The average number of days per month used in calculations.
+/// * `total_months`: The total number of months to calculate depletion for.
+///
+/// # Returns
+///
+/// A Vec of tuples, where each tuple contains a date and the corresponding monthly streamflow depletion in acre-ft/month.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] if the pumping, parameters or horizon are invalid; see
+/// [`calculate_streamflow_depletion`].
+pub fn calculate_streamflow_depletion_hunt_1999(
+    pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in acre-ft / month
+    distance_to_well: f64,
+    specific_yield: f64,
+    transmissivity: f64,
+    streambed_conductance: f64,
+    days_per_month: f64,
+    total_months: usize,
+) -> Result<Vec<(NaiveDate, f64)>, DepletionError> {
+    let model = Hunt1999::new(
+        distance_to_well,
+        specific_yield,
+        transmissivity,
+        streambed_conductance,
+    );
+    calculate_streamflow_depletion(
+        &model,
+        pumping_volumes_monthly,
+        days_per_month,
+        total_months,
+    )
+}
+
+/// The Hunt (1999) solution for a well near a stream with a resistive streambed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hunt1999 {
+    /// Distance from the well to the stream (in length units, typically feet).
+    pub distance_to_well: f64,
+    /// Storativity or specific yield of the aquifer (dimensionless).
+    pub specific_yield: f64,
+    /// Transmissivity of the aquifer (in length²/time units, typically ft²/day).
+    pub transmissivity: f64,
+    /// Streambed conductance, lambda (in length/time units, typically ft/day). This is the
+    /// streambed hydraulic conductivity times the stream width divided by the streambed thickness.
+    pub streambed_conductance: f64,
+}
+
+impl Hunt1999 {
+    pub fn new(
+        distance_to_well: f64,
+        specific_yield: f64,
+        transmissivity: f64,
+        streambed_conductance: f64,
+    ) -> Self {
+        Hunt1999 {
+            distance_to_well,
+            specific_yield,
+            transmissivity,
+            streambed_conductance,
+        }
+    }
+
+    /// Creates the Hunt (1999) model from validated aquifer parameters.
+    ///
+    /// # Parameters
+    ///
+    /// * `distance_to_well`: Distance from the well to the stream.
+    /// * `aquifer`: The validated storage and transmissivity of the aquifer.
+    /// * `streambed_conductance`: Streambed conductance, lambda, which has the units of a conductivity.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DepletionError`] if the distance is negative or the conductance is not greater than zero.
+    pub fn from_aquifer(
+        distance_to_well: Length,
+        aquifer: &AquiferParams,
+        streambed_conductance: Conductivity,
+    ) -> Result<Self, DepletionError> {
+        let model = Hunt1999::new(
+            distance_to_well.value(LengthUnit::Feet),
+            aquifer.specific_yield(),
+            aquifer.transmissivity(),
+            streambed_conductance.value(ConductivityUnit::FeetPerDay),
+        );
+        model.validate()?;
+        Ok(model)
+    }
+}
+
+impl DepletionModel for Hunt1999 {
+    fn name(&self) -> &'static str {
+        "Hunt (1999) streambed conductance"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("distance to well", self.distance_to_well),
+            ("specific yield", self.specific_yield),
+            ("transmissivity", self.transmissivity),
+            ("streambed conductance", self.streambed_conductance),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), DepletionError> {
+        check_non_negative("distance to well", self.distance_to_well)?;
+        AquiferParams::new(self.specific_yield, self.transmissivity)?;
+        check_positive("streambed conductance", self.streambed_conductance)
+    }
+
+    /// Calculates the depletion fraction using the Hunt (1999) solution.
+    ///
+    /// The solution is
+    /// `erfc(z) - exp(w² + 2zw) * erfc(w + z)` with `z = sqrt(S d² / 4Tt)` and
+    /// `w = sqrt(λ² t / 4ST)`. The second term is evaluated as `exp(-z²) * erfcx(w + z)` so that
+    /// it stays finite for large conductance, where the solution reduces to the Glover result.
+    ///
+    /// # Parameters
+    ///
+    /// * `time`: Time since pumping began (in time units, typically days).
+    ///
+    /// # Returns
+    ///
+    /// Returns the depletion fraction as a `f64`, representing the proportion of pumping
+    /// that has been captured from the stream at the given time.
+    fn depletion_fraction(&self, time: f64) -> f64 {
+        if time <= 0.0 {
+            return 0.0;
+        }
+        let z = ((self.specific_yield * self.distance_to_well.powi(2))
+            / (4.0 * self.transmissivity * time))
+            .sqrt();
+        let w = ((self.streambed_conductance.powi(2) * time)
+            / (4.0 * self.specific_yield * self.transmissivity))
+            .sqrt();
+        erfc(z) - (-z * z).exp() * erfcx(z + w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_streamflow_depletion_infinite;
+
+    #[test]
+    fn test_hunt_reduces_to_glover() {
+        // Aquifer parameters (in feet-based units)
+        let d: f64 = 4000.0; // Distance to stream (ft)
+        let s: f64 = 0.2; // Storativity (dimensionless)
+        let t: f64 = 261_800.0 / 7.481; // Transmissivity (ft²/day)
+
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0); // acre-feet for month 1
+        let days_per_month = 30.42; // Average days per month
+        let total_months = 120; // 10 years
+
+        let glover = calculate_streamflow_depletion_infinite(
+            &pumping_volumes,
+            d,
+            s,
+            t,
+            days_per_month,
+            total_months,
+        )
+        .unwrap();
+        let value = calculate_streamflow_depletion_hunt_1999(
+            &pumping_volumes,
+            d,
+            s,
+            t,
+            1.0e12,
+            days_per_month,
+            total_months,
+        )
+        .unwrap();
+
+        assert_eq!(value.len(), glover.len());
+        for (hunt, glover) in value.iter().zip(glover.iter()) {
+            assert_eq!(hunt.0, glover.0);
+            assert!((hunt.1 - glover.1).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_streambed_reduces_early_depletion() {
+        let d: f64 = 4000.0;
+        let s: f64 = 0.2;
+        let t: f64 = 261_800.0 / 7.481;
+        let glover = crate::GloverInfinite::new(d, s, t);
+        let hunt = Hunt1999::new(d, s, t, 10.0);
+
+        for time in [30.0, 365.0, 3650.0] {
+            let hunt_fraction = hunt.depletion_fraction(time);
+            assert!(hunt_fraction > 0.0);
+            assert!(hunt_fraction < glover.depletion_fraction(time));
+        }
+        assert_eq!(hunt.depletion_fraction(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_from_aquifer() {
+        let aquifer = AquiferParams::new(0.2, 35_000.0).unwrap();
+        let model = Hunt1999::from_aquifer(
+            Length::meters(1219.2),
+            &aquifer,
+            Conductivity::meters_per_day(6.096),
+        )
+        .unwrap();
+        assert!((model.distance_to_well - 4000.0).abs() < 1e-9);
+        assert!((model.streambed_conductance - 20.0).abs() < 1e-9);
+        assert!(
+            Hunt1999::from_aquifer(
+                Length::feet(4000.0),
+                &aquifer,
+                Conductivity::feet_per_day(0.0)
+            )
+            .is_err()
+        );
+    }
+}
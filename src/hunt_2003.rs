@@ -0,0 +1,319 @@
+use crate::engine::calculate_streamflow_depletion;
+use crate::error::{DepletionError, check_non_negative, check_positive};
+use crate::laplace::Stehfest;
+use crate::model::DepletionModel;
+use crate::params::AquiferParams;
+use crate::units::{Conductivity, ConductivityUnit, Length, LengthUnit, Storage};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+/// Calculates streamflow depletion for a semiconfined aquifer using the Hunt (2003) solution.
+///
+/// This function computes the monthly streamflow depletion for a well screened in a confined aquifer
+/// that is overlain by an aquitard. The aquitard has a water table at its top and leaks to the
+/// pumped aquifer, and the stream is connected to the aquifer through a streambed of finite conductance.
+///
+/// # Parameters
+///
+/// * `pumping_volumes_monthly`: A HashMap containing monthly pumping volumes in acre-ft/month, keyed by date.
+/// * `distance_to_well`: The distance from the well to the stream in feet.
+/// * `storativity`: The storativity of the pumped aquifer (dimensionless).
+/// * `transmissivity`: The transmissivity of the pumped aquifer in ft²/day.
+/// * `aquitard_thickness`: The saturated thickness of the aquitard in feet.
+/// * `aquitard_vertical_conductivity`: The vertical hydraulic conductivity of the aquitard in ft/day.
+/// * `aquitard_specific_yield`: The specific yield of the aquitard (dimensionless).
+/// * `streambed_conductance`: The streambed conductance (lambda) in ft/day.
+/// * `days_per_month`: The average number of days per month used in calculations.
+/// * `total_months`: The total number of months to calculate depletion for.
+///
+/// # Returns
+///
+/// A Vec of tuples, where each tuple contains a date and the corresponding monthly streamflow depletion in acre-ft/month.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] if the pumping, parameters or horizon are invalid; see
+/// [`calculate_streamflow_depletion`].
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_streamflow_depletion_hunt_2003(
+    pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in acre-ft / month
+    distance_to_well: f64,
+    storativity: f64,
+    transmissivity: f64,
+    aquitard_thickness: f64,
+    aquitard_vertical_conductivity: f64,
+    aquitard_specific_yield: f64,
+    streambed_conductance: f64,
+    days_per_month: f64,
+    total_months: usize,
+) -> Result<Vec<(NaiveDate, f64)>, DepletionError> {
+    let model = Hunt2003::new(
+        distance_to_well,
+        storativity,
+        transmissivity,
+        aquitard_thickness,
+        aquitard_vertical_conductivity,
+        aquitard_specific_yield,
+        streambed_conductance,
+    );
+    calculate_streamflow_depletion(
+        &model,
+        pumping_volumes_monthly,
+        days_per_month,
+        total_months,
+    )
+}
+
+/// The Hunt (2003) solution for a well in a semiconfined (leaky) aquifer beneath an aquitard.
+///
+/// The solution is only available in the Laplace domain and is inverted numerically.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hunt2003 {
+    /// Distance from the well to the stream (in length units, typically feet).
+    pub distance_to_well: f64,
+    /// Storativity of the pumped aquifer (dimensionless).
+    pub storativity: f64,
+    /// Transmissivity of the pumped aquifer (in length²/time units, typically ft²/day).
+    pub transmissivity: f64,
+    /// Saturated thickness of the aquitard (in length units, typically feet).
+    pub aquitard_thickness: f64,
+    /// Vertical hydraulic conductivity of the aquitard (in length/time units, typically ft/day).
+    pub aquitard_vertical_conductivity: f64,
+    /// Specific yield of the water table at the top of the aquitard (dimensionless).
+    pub aquitard_specific_yield: f64,
+    /// Streambed conductance, lambda (in length/time units, typically ft/day).
+    pub streambed_conductance: f64,
+    /// The numerical Laplace inverter used to evaluate the solution.
+    pub inverter: Stehfest,
+}
+
+impl Hunt2003 {
+    pub fn new(
+        distance_to_well: f64,
+        storativity: f64,
+        transmissivity: f64,
+        aquitard_thickness: f64,
+        aquitard_vertical_conductivity: f64,
+        aquitard_specific_yield: f64,
+        streambed_conductance: f64,
+    ) -> Self {
+        Hunt2003 {
+            distance_to_well,
+            storativity,
+            transmissivity,
+            aquitard_thickness,
+            aquitard_vertical_conductivity,
+            aquitard_specific_yield,
+            streambed_conductance,
+            inverter: Stehfest::default(),
+        }
+    }
+
+    /// Creates the Hunt (2003) model from validated aquifer parameters.
+    ///
+    /// # Parameters
+    ///
+    /// * `distance_to_well`: Distance from the well to the stream.
+    /// * `aquifer`: The validated storativity and transmissivity of the pumped aquifer.
+    /// * `aquitard_thickness`: Saturated thickness of the aquitard.
+    /// * `aquitard_vertical_conductivity`: Vertical hydraulic conductivity of the aquitard.
+    /// * `aquitard_specific_yield`: Specific yield of the water table at the top of the aquitard.
+    /// * `streambed_conductance`: Streambed conductance, lambda, which has the units of a conductivity.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DepletionError`] for the first parameter out of range, as in [`DepletionModel::validate`].
+    pub fn from_aquifer(
+        distance_to_well: Length,
+        aquifer: &AquiferParams,
+        aquitard_thickness: Length,
+        aquitard_vertical_conductivity: Conductivity,
+        aquitard_specific_yield: Storage,
+        streambed_conductance: Conductivity,
+    ) -> Result<Self, DepletionError> {
+        let model = Hunt2003::new(
+            distance_to_well.value(LengthUnit::Feet),
+            aquifer.specific_yield(),
+            aquifer.transmissivity(),
+            aquitard_thickness.value(LengthUnit::Feet),
+            aquitard_vertical_conductivity.value(ConductivityUnit::FeetPerDay),
+            aquitard_specific_yield.0,
+            streambed_conductance.value(ConductivityUnit::FeetPerDay),
+        );
+        model.validate()?;
+        Ok(model)
+    }
+
+    /// Evaluates the Laplace transform of the depletion fraction.
+    ///
+    /// Integrating the flow equation along the stream gives a one-dimensional problem whose
+    /// transform is `λ exp(-k d) / (p (λ + 2 k T))`, where
+    /// `k² = (S p + ε Sy p / (Sy p + ε)) / T` and `ε = K' / b'` is the aquitard leakance.
+    ///
+    /// # Parameters
+    ///
+    /// * `p`: The Laplace variable (in 1/time units, typically 1/day).
+    fn depletion_fraction_transform(&self, p: f64) -> f64 {
+        let leakance = self.aquitard_vertical_conductivity / self.aquitard_thickness;
+        let sy_p = self.aquitard_specific_yield * p;
+        let leakage = if leakance > 0.0 {
+            leakance * sy_p / (sy_p + leakance)
+        } else {
+            0.0
+        };
+        let k = ((self.storativity * p + leakage) / self.transmissivity).sqrt();
+        self.streambed_conductance * (-k * self.distance_to_well).exp()
+            / (p * (self.streambed_conductance + 2.0 * k * self.transmissivity))
+    }
+}
+
+impl DepletionModel for Hunt2003 {
+    fn name(&self) -> &'static str {
+        "Hunt (2003) semiconfined aquifer"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("distance to well", self.distance_to_well),
+            ("storativity", self.storativity),
+            ("transmissivity", self.transmissivity),
+            ("aquitard thickness", self.aquitard_thickness),
+            (
+                "aquitard vertical conductivity",
+                self.aquitard_vertical_conductivity,
+            ),
+            ("aquitard specific yield", self.aquitard_specific_yield),
+            ("streambed conductance", self.streambed_conductance),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), DepletionError> {
+        check_non_negative("distance to well", self.distance_to_well)?;
+        AquiferParams::new(self.storativity, self.transmissivity)?;
+        check_positive("aquitard thickness", self.aquitard_thickness)?;
+        check_non_negative(
+            "aquitard vertical conductivity",
+            self.aquitard_vertical_conductivity,
+        )?;
+        check_non_negative("aquitard specific yield", self.aquitard_specific_yield)?;
+        check_positive("streambed conductance", self.streambed_conductance)
+    }
+
+    /// Calculates the depletion fraction using the Hunt (2003) solution.
+    ///
+    /// # Parameters
+    ///
+    /// * `time`: Time since pumping began (in time units, typically days).
+    ///
+    /// # Returns
+    ///
+    /// Returns the depletion fraction as a `f64`, representing the proportion of pumping
+    /// that has been captured from the stream at the given time.
+    fn depletion_fraction(&self, time: f64) -> f64 {
+        if time <= 0.0 {
+            return 0.0;
+        }
+        self.inverter
+            .invert(|p| self.depletion_fraction_transform(p), time)
+            .clamp(0.0, 1.0)
+    }
+
+    /// Calculates the depletion volume per unit pumping rate.
+    ///
+    /// Integrating in time divides the transform by `p`, so the volume is inverted directly
+    /// rather than integrated numerically.
+    ///
+    /// # Parameters
+    ///
+    /// * `time`: Time since pumping began (in time units, typically days).
+    fn depletion_volume(&self, time: f64) -> f64 {
+        if time <= 0.0 {
+            return 0.0;
+        }
+        self.inverter
+            .invert(|p| self.depletion_fraction_transform(p) / p, time)
+            .clamp(0.0, time)
+    }
+
+    fn volume_response(&self, total_days: usize) -> Vec<f64> {
+        (0..total_days)
+            .map(|m| self.depletion_volume(m as f64))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hunt_1999::Hunt1999;
+
+    #[test]
+    fn test_without_leakage_matches_hunt_1999() {
+        let d: f64 = 4000.0;
+        let s: f64 = 0.2;
+        let t: f64 = 261_800.0 / 7.481;
+        let lambda = 10.0;
+        let hunt_1999 = Hunt1999::new(d, s, t, lambda);
+        let hunt_2003 = Hunt2003::new(d, s, t, 20.0, 0.0, 0.1, lambda);
+
+        for time in [10.0, 30.0, 365.0, 3650.0] {
+            let expected = hunt_1999.depletion_fraction(time);
+            assert!((hunt_2003.depletion_fraction(time) - expected).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_leaky_aquifer_depletion() {
+        // Confined aquifer beneath a 20 ft aquitard
+        let d: f64 = 1000.0; // Distance to stream (ft)
+        let s: f64 = 0.0005; // Storativity (dimensionless)
+        let t: f64 = 5000.0; // Transmissivity (ft²/day)
+
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0); // acre-feet for month 1
+        let days_per_month = 30.42; // Average days per month
+        let total_months = 120; // 10 years
+
+        let value = calculate_streamflow_depletion_hunt_2003(
+            &pumping_volumes,
+            d,
+            s,
+            t,
+            20.0,
+            0.01,
+            0.1,
+            5.0,
+            days_per_month,
+            total_months,
+        )
+        .unwrap();
+
+        assert!(value.len() <= total_months);
+        let total_depletion: f64 = value.iter().map(|(_, v)| v).sum();
+        assert!(total_depletion > 0.0);
+        assert!(total_depletion < 100.0);
+
+        // Leakage from the aquitard supplies water that would otherwise come from the stream
+        let leaky = Hunt2003::new(d, s, t, 20.0, 0.01, 0.1, 5.0);
+        let confined = Hunt2003::new(d, s, t, 20.0, 0.0, 0.1, 5.0);
+        assert!(leaky.depletion_fraction(30.0) < confined.depletion_fraction(30.0));
+    }
+
+    #[test]
+    fn test_from_aquifer() {
+        let aquifer = AquiferParams::new(0.001, 35_000.0).unwrap();
+        let model = Hunt2003::from_aquifer(
+            Length::meters(1219.2),
+            &aquifer,
+            Length::meters(3.048),
+            Conductivity::meters_per_day(0.03048),
+            Storage(0.1),
+            Conductivity::meters_per_day(6.096),
+        )
+        .unwrap();
+        assert!((model.distance_to_well - 4000.0).abs() < 1e-9);
+        assert!((model.aquitard_thickness - 10.0).abs() < 1e-9);
+        assert!((model.aquitard_vertical_conductivity - 0.1).abs() < 1e-12);
+        assert!((model.streambed_conductance - 20.0).abs() < 1e-9);
+    }
+}
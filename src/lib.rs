@@ -1,11 +1,64 @@
+pub mod anisotropy;
+pub mod engine;
+pub mod error;
+mod fft;
 pub mod glover_alluvial;
 pub mod glover_infinite;
+pub mod hantush;
+pub mod hunt_1999;
+pub mod hunt_2003;
+pub mod laplace;
+pub mod metrics;
+pub mod model;
+pub mod parallel_streams;
+pub mod params;
+pub mod result;
 pub mod sdf;
+pub mod series;
+pub mod solver;
+pub mod stream_reach;
+pub mod units;
 pub mod urf;
 pub mod utils;
+pub mod wedge;
 
-pub use glover_alluvial::calculate_streamflow_depletion_alluvial;
-pub use glover_infinite::calculate_streamflow_depletion_infinite;
-pub use sdf::calculate_streamflow_depletion_sdf;
-pub use urf::{LaggedUrfByDate, LaggedUrfResult, UrfValue, combined_urf_results, urf_lagging};
+pub use anisotropy::Anisotropy;
+pub use engine::{
+    ConvolutionMethod, DepletionByStream, DepletionOptions, Horizon, NegativeHandling,
+    ReportingOptions, calculate_streamflow_depletion, calculate_streamflow_depletion_by_stream,
+    calculate_streamflow_depletion_daily, calculate_streamflow_depletion_periods,
+    calculate_streamflow_depletion_series, calculate_streamflow_depletion_with_options,
+};
+pub use error::DepletionError;
+pub use glover_alluvial::{
+    AlluvialDepletion, BoundaryCapture, BoundaryType, GloverAlluvial, ParallelBoundaryGeometry,
+    StreamSide, calculate_alluvial_depletion_split, calculate_streamflow_depletion_alluvial,
+    calculate_streamflow_depletion_valley,
+};
+pub use glover_infinite::{GloverInfinite, calculate_streamflow_depletion_infinite};
+pub use hantush::{
+    Hantush1965, StreambedComparison, calculate_streambed_comparison,
+    calculate_streamflow_depletion_hantush, streambed_leakance,
+};
+pub use hunt_1999::{Hunt1999, calculate_streamflow_depletion_hunt_1999};
+pub use hunt_2003::{Hunt2003, calculate_streamflow_depletion_hunt_2003};
+pub use laplace::Stehfest;
+pub use metrics::{MonthlyMetrics, depletion_metrics, depletion_metrics_from_results};
+pub use model::DepletionModel;
+pub use parallel_streams::{ParallelStreams, calculate_streamflow_depletion_parallel_streams};
+pub use params::{AquiferParams, ParameterWarning};
+pub use result::{DepletionResult, calculate_streamflow_depletion_result};
+pub use sdf::{StreamDepletionFactor, calculate_streamflow_depletion_sdf};
+pub use series::{DailySeries, MonthlySeries, PeriodSeries};
+pub use solver::{DepletionMeasure, time_to_depletion_fraction, time_to_depletion_rate};
+pub use stream_reach::{StreamReach, calculate_streamflow_depletion_stream_reach};
+pub use units::{
+    Conductivity, ConductivityUnit, Length, LengthUnit, Rate, RateUnit, Storage, Transmissivity,
+    TransmissivityUnit, Volume, VolumeUnit,
+};
+pub use urf::{
+    LaggedUrfByDate, LaggedUrfResult, UrfValue, combined_urf_results,
+    combined_urf_results_with_reporting, urf_lagging,
+};
 pub use utils::add_months;
+pub use wedge::{Wedge, WedgeBranch, WedgeStream, calculate_streamflow_depletion_wedge};
@@ -0,0 +1,173 @@
+//! Derived depletion metrics, such as cumulative depletion and the fraction of pumping captured.
+//!
+//! The metrics are calculated from monthly pumping and depletion on the same month alignment
+//! used by the engine, so they can be applied to the output of any model or of the URF method.
+
+use crate::series::MonthlySeries;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The pumping, depletion and derived metrics for one month.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonthlyMetrics {
+    /// The first day of the month.
+    pub date: NaiveDate,
+    /// The volume pumped in the month.
+    pub pumping: f64,
+    /// The volume depleted from the stream in the month.
+    pub depletion: f64,
+    /// The volume pumped up to the end of the month.
+    pub cumulative_pumping: f64,
+    /// The volume depleted from the stream up to the end of the month.
+    pub cumulative_depletion: f64,
+    /// The cumulative depletion as a percent of the cumulative pumping, or `None` before any pumping.
+    pub percent_of_pumping: Option<f64>,
+    /// The depletion in the month as a fraction of the pumping in the month, or `None` in months
+    /// without pumping. Under constant pumping this is the average depletion fraction over the month.
+    pub capture_fraction: Option<f64>,
+}
+
+/// Calculates the cumulative depletion, percent of pumping and capture fraction in each month.
+///
+/// # Parameters
+///
+/// * `pumping`: The monthly pumping volumes.
+/// * `depletion`: The monthly depletion volumes in the same unit as the pumping.
+///
+/// # Returns
+///
+/// A Vec with the metrics for every month from the first to the last month of either series,
+/// in date order. Months missing from a series are zero.
+pub fn depletion_metrics(
+    pumping: &MonthlySeries,
+    depletion: &MonthlySeries,
+) -> Vec<MonthlyMetrics> {
+    let series = [pumping, depletion];
+    let Some(start_date) = series
+        .iter()
+        .filter(|series| !series.is_empty())
+        .map(|series| series.start_date())
+        .min()
+    else {
+        return Vec::new();
+    };
+    let start = MonthlySeries::zeros(start_date, 0);
+    let len = series
+        .iter()
+        .filter(|series| !series.is_empty())
+        .map(|series| start.month_index(series.start_date()) + series.len() as i64)
+        .max()
+        .unwrap_or(0) as usize;
+    let pumping = pumping.window(start_date, len);
+    let depletion = depletion.window(start_date, len);
+
+    let mut cumulative_pumping = 0.0;
+    let mut cumulative_depletion = 0.0;
+    pumping
+        .iter()
+        .zip(depletion.values())
+        .map(|((date, pumping), depletion)| {
+            cumulative_pumping += pumping;
+            cumulative_depletion += depletion;
+            MonthlyMetrics {
+                date,
+                pumping,
+                depletion: *depletion,
+                cumulative_pumping,
+                cumulative_depletion,
+                percent_of_pumping: (cumulative_pumping > 0.0)
+                    .then(|| 100.0 * cumulative_depletion / cumulative_pumping),
+                capture_fraction: (pumping > 0.0).then(|| depletion / pumping),
+            }
+        })
+        .collect()
+}
+
+/// Calculates the depletion metrics from the pumping and `Vec` results of a depletion function.
+///
+/// This accepts the input and output of [`crate::calculate_streamflow_depletion_infinite`],
+/// [`crate::calculate_streamflow_depletion_alluvial`], [`crate::calculate_streamflow_depletion_sdf`]
+/// and the URF functions, where months below the reporting threshold may be missing.
+///
+/// # Parameters
+///
+/// * `pumping_volumes_monthly`: A HashMap of monthly pumping volumes keyed by any day of the month.
+/// * `depletion`: The monthly depletion in the same unit as the pumping.
+///
+/// # Returns
+///
+/// The metrics for each month as in [`depletion_metrics`].
+pub fn depletion_metrics_from_results(
+    pumping_volumes_monthly: &HashMap<NaiveDate, f64>,
+    depletion: &[(NaiveDate, f64)],
+) -> Vec<MonthlyMetrics> {
+    let pumping = MonthlySeries::from(pumping_volumes_monthly);
+    let mut depletion_series = MonthlySeries::default();
+    for (date, value) in depletion {
+        depletion_series.add(*date, *value);
+    }
+    depletion_metrics(&pumping, &depletion_series)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glover_infinite::calculate_streamflow_depletion_infinite;
+    use crate::urf::{UrfValue, combined_urf_results, urf_lagging};
+
+    fn date(year: i32, month: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, 1).unwrap()
+    }
+
+    #[test]
+    fn test_depletion_metrics() {
+        let pumping = MonthlySeries::new(date(2025, 1), vec![100.0, 0.0, 50.0]);
+        let depletion = MonthlySeries::new(date(2025, 2), vec![20.0, 30.0, 40.0]);
+
+        let metrics = depletion_metrics(&pumping, &depletion);
+        assert_eq!(metrics.len(), 4);
+        assert_eq!(metrics[0].percent_of_pumping, Some(0.0));
+        assert_eq!(metrics[0].capture_fraction, Some(0.0));
+        assert_eq!(metrics[1].capture_fraction, None);
+        assert_eq!(metrics[2].cumulative_depletion, 50.0);
+        assert_eq!(metrics[2].capture_fraction, Some(0.6));
+        assert_eq!(metrics[3].date, date(2025, 4));
+        assert_eq!(metrics[3].cumulative_pumping, 150.0);
+        assert_eq!(metrics[3].percent_of_pumping, Some(60.0));
+
+        assert!(depletion_metrics(&MonthlySeries::default(), &MonthlySeries::default()).is_empty());
+    }
+
+    #[test]
+    fn test_metrics_from_results() {
+        let pumping: HashMap<NaiveDate, f64> =
+            (1..=12).map(|month| (date(2025, month), 100.0)).collect();
+        let depletion =
+            calculate_streamflow_depletion_infinite(&pumping, 1000.0, 0.2, 35_000.0, 30.42, 24)
+                .unwrap();
+        let metrics = depletion_metrics_from_results(&pumping, &depletion);
+        assert_eq!(metrics[0].date, date(2025, 1));
+        // the capture fraction rises towards 1 under constant pumping, varying with the month lengths
+        let capture: Vec<f64> = metrics[..12]
+            .iter()
+            .map(|month| month.capture_fraction.unwrap())
+            .collect();
+        assert!(capture[0] < capture[5] && capture[5] < capture[11]);
+        assert!(capture[11] > 0.9 && capture[11] < 1.0);
+        // after pumping stops the percent of pumping keeps rising as the stream is depleted
+        assert!(metrics[12..].windows(2).all(|pair| {
+            pair[0].percent_of_pumping.unwrap() < pair[1].percent_of_pumping.unwrap()
+        }));
+        assert!(metrics[23].percent_of_pumping.unwrap() > 90.0);
+
+        let usage = HashMap::from([(date(2025, 3), 10.0)]);
+        let urf = vec![UrfValue::new(1, 1, 0.6), UrfValue::new(2, 1, 0.4)];
+        let lagged = combined_urf_results(urf_lagging(&usage, urf));
+        let metrics = depletion_metrics_from_results(&usage, &lagged);
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(metrics[0].capture_fraction, Some(0.6));
+        assert_eq!(metrics[1].cumulative_depletion, 10.0);
+        assert_eq!(metrics[1].percent_of_pumping, Some(100.0));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::error::DepletionError;
+use crate::utils::simpson;
+
+/// A stream depletion model expressed as its depletion fraction over time.
+///
+/// Every analytical method in this crate reduces to a function giving the fraction of a
+/// constant pumping rate that is being captured from the stream at a given time since
+/// pumping began. The shared convolution engine in [`crate::engine`] superimposes this
+/// response over a pumping history, so any type implementing this trait can be used
+/// interchangeably, including behind a `Box<dyn DepletionModel>`.
+pub trait DepletionModel {
+    /// Returns the depletion fraction at `time` (in days) since pumping began.
+    ///
+    /// The value should range from 0 (no depletion) towards 1 (all pumping captured
+    /// from the stream).
+    fn depletion_fraction(&self, time: f64) -> f64;
+
+    /// Checks that the parameters of the model are physically possible.
+    ///
+    /// The engine calls this before evaluating the model. The default accepts any parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DepletionError::NonPhysicalParameter`] for the first parameter out of range.
+    fn validate(&self) -> Result<(), DepletionError> {
+        Ok(())
+    }
+
+    /// Returns a short description of the method, used to identify the model in reports.
+    fn name(&self) -> &'static str {
+        "custom model"
+    }
+
+    /// Returns the name and value of each parameter of the model, used in reports.
+    ///
+    /// The default reports no parameters.
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        Vec::new()
+    }
+
+    /// Returns the depletion fraction approached after a very long time of pumping.
+    ///
+    /// The default is 1, meaning all of the pumping is eventually captured from the stream.
+    /// Models that share the pumping with another stream or boundary override it.
+    fn ultimate_fraction(&self) -> f64 {
+        1.0
+    }
+
+    /// Returns the depletion fraction for each whole day from 0 up to `total_days`.
+    ///
+    /// # Parameters
+    ///
+    /// * `total_days`: The number of daily time steps to evaluate.
+    ///
+    /// # Returns
+    ///
+    /// A Vec of length `total_days` where element `m` is the depletion fraction at day `m`.
+    fn unit_response(&self, total_days: usize) -> Vec<f64> {
+        (0..total_days)
+            .map(|m| self.depletion_fraction(m as f64))
+            .collect()
+    }
+
+    /// Returns the depletion volume per unit pumping rate at `time` (in days) since pumping began.
+    ///
+    /// This is the integral of the depletion fraction from 0 to `time`, so multiplying it by a
+    /// constant pumping rate gives the total volume taken from the stream. The default integrates
+    /// [`DepletionModel::depletion_fraction`] numerically; models with a closed-form volume
+    /// function override it.
+    fn depletion_volume(&self, time: f64) -> f64 {
+        if time <= 0.0 {
+            return 0.0;
+        }
+        // substitute τ = time * s² to place more points early, where the response changes fastest
+        simpson(
+            |s| 2.0 * time * s * self.depletion_fraction(time * s * s),
+            0.0,
+            1.0,
+            256,
+        )
+    }
+
+    /// Returns the depletion volume per unit pumping rate for each whole day from 0 up to `total_days`.
+    ///
+    /// # Parameters
+    ///
+    /// * `total_days`: The number of daily time steps to evaluate.
+    ///
+    /// # Returns
+    ///
+    /// A Vec of length `total_days` where element `m` is the depletion volume at day `m`. The
+    /// default accumulates the depletion fraction day by day with Simpson's rule, which only needs
+    /// two evaluations per day. Models with a closed-form volume function override it with
+    /// [`DepletionModel::depletion_volume`].
+    fn volume_response(&self, total_days: usize) -> Vec<f64> {
+        let mut volume = 0.0;
+        let mut fraction = self.depletion_fraction(0.0);
+        (0..total_days)
+            .map(|m| {
+                if m > 0 {
+                    let day = m as f64;
+                    let next_fraction = self.depletion_fraction(day);
+                    volume +=
+                        (fraction + 4.0 * self.depletion_fraction(day - 0.5) + next_fraction) / 6.0;
+                    fraction = next_fraction;
+                }
+                volume
+            })
+            .collect()
+    }
+}
+
+impl<M: DepletionModel + ?Sized> DepletionModel for Box<M> {
+    fn depletion_fraction(&self, time: f64) -> f64 {
+        (**self).depletion_fraction(time)
+    }
+
+    fn validate(&self) -> Result<(), DepletionError> {
+        (**self).validate()
+    }
+
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        (**self).parameters()
+    }
+
+    fn ultimate_fraction(&self) -> f64 {
+        (**self).ultimate_fraction()
+    }
+
+    fn unit_response(&self, total_days: usize) -> Vec<f64> {
+        (**self).unit_response(total_days)
+    }
+
+    fn depletion_volume(&self, time: f64) -> f64 {
+        (**self).depletion_volume(time)
+    }
+
+    fn volume_response(&self, total_days: usize) -> Vec<f64> {
+        (**self).volume_response(total_days)
+    }
+}
+
+impl<M: DepletionModel + ?Sized> DepletionModel for &M {
+    fn depletion_fraction(&self, time: f64) -> f64 {
+        (**self).depletion_fraction(time)
+    }
+
+    fn validate(&self) -> Result<(), DepletionError> {
+        (**self).validate()
+    }
+
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        (**self).parameters()
+    }
+
+    fn ultimate_fraction(&self) -> f64 {
+        (**self).ultimate_fraction()
+    }
+
+    fn unit_response(&self, total_days: usize) -> Vec<f64> {
+        (**self).unit_response(total_days)
+    }
+
+    fn depletion_volume(&self, time: f64) -> f64 {
+        (**self).depletion_volume(time)
+    }
+
+    fn volume_response(&self, total_days: usize) -> Vec<f64> {
+        (**self).volume_response(total_days)
+    }
+}
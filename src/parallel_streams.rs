@@ -0,0 +1,232 @@
+use crate::engine::{DepletionByStream, calculate_streamflow_depletion_by_stream};
+use crate::error::{DepletionError, check_positive};
+use crate::glover_alluvial::{BoundaryCapture, BoundaryType, GloverAlluvial};
+use crate::model::DepletionModel;
+use crate::params::AquiferParams;
+use crate::units::{Length, LengthUnit};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+/// Calculates streamflow depletion for a well located between two parallel streams.
+///
+/// Both streams are fully penetrating and act as constant-head boundaries, so the pumping is
+/// split between them and their combined depletion approaches the pumping rate at steady state.
+///
+/// # Parameters
+///
+/// * `pumping_volumes_monthly`: A HashMap containing monthly pumping volumes in acre-ft/month, keyed by date.
+/// * `streams`: The aquifer between the two streams.
+/// * `first_stream`: The identifier used for the first stream in the results.
+/// * `second_stream`: The identifier used for the second stream in the results.
+/// * `days_per_month`: The average number of days per month used in calculations.
+/// * `total_months`: The total number of months to calculate depletion for.
+///
+/// # Returns
+///
+/// A [`DepletionByStream`] with the monthly depletion of each stream in acre-ft/month, keyed by
+/// the stream identifiers.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] if the two stream identifiers are the same or the pumping,
+/// parameters or horizon are invalid; see
+/// [`calculate_streamflow_depletion`](crate::calculate_streamflow_depletion).
+pub fn calculate_streamflow_depletion_parallel_streams(
+    pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in acre-ft / month
+    streams: &ParallelStreams,
+    first_stream: i32,
+    second_stream: i32,
+    days_per_month: f64,
+    total_months: usize,
+) -> Result<DepletionByStream, DepletionError> {
+    let first = streams.first_stream();
+    let second = streams.second_stream();
+    calculate_streamflow_depletion_by_stream(
+        &[(first_stream, &first), (second_stream, &second)],
+        pumping_volumes_monthly,
+        days_per_month,
+        total_months,
+    )
+}
+
+/// An aquifer strip between two parallel, fully penetrating streams with a well between them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParallelStreams {
+    /// Distance from the well to the first stream (in length units, typically feet).
+    pub distance_to_first_stream: f64,
+    /// Distance between the two streams (in length units, typically feet).
+    pub distance_between_streams: f64,
+    /// Storativity or specific yield of the aquifer (dimensionless).
+    pub specific_yield: f64,
+    /// Transmissivity of the aquifer (in length²/time units, typically ft²/day).
+    pub transmissivity: f64,
+}
+
+impl ParallelStreams {
+    pub fn new(
+        distance_to_first_stream: f64,
+        distance_between_streams: f64,
+        specific_yield: f64,
+        transmissivity: f64,
+    ) -> Self {
+        ParallelStreams {
+            distance_to_first_stream,
+            distance_between_streams,
+            specific_yield,
+            transmissivity,
+        }
+    }
+
+    /// Creates the model from validated aquifer parameters.
+    ///
+    /// # Parameters
+    ///
+    /// * `distance_to_first_stream`: Distance from the well to the first stream.
+    /// * `distance_between_streams`: Distance between the two streams.
+    /// * `aquifer`: The validated storage and transmissivity of the aquifer.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DepletionError`] if the distance between the streams is not greater than zero
+    /// or the well is not between the streams.
+    pub fn from_aquifer(
+        distance_to_first_stream: Length,
+        distance_between_streams: Length,
+        aquifer: &AquiferParams,
+    ) -> Result<Self, DepletionError> {
+        let model = ParallelStreams::new(
+            distance_to_first_stream.value(LengthUnit::Feet),
+            distance_between_streams.value(LengthUnit::Feet),
+            aquifer.specific_yield(),
+            aquifer.transmissivity(),
+        );
+        model.validate()?;
+        Ok(model)
+    }
+
+    /// Returns the depletion model of the first stream.
+    ///
+    /// This is the alluvial image-well solution with the second stream as a constant-head
+    /// boundary behind the well.
+    pub fn first_stream(&self) -> GloverAlluvial {
+        GloverAlluvial::new(
+            self.distance_to_first_stream,
+            self.distance_between_streams,
+            self.specific_yield,
+            self.transmissivity,
+        )
+        .with_boundary_type(BoundaryType::ConstantHead)
+    }
+
+    /// Returns the depletion model of the second stream.
+    pub fn second_stream(&self) -> BoundaryCapture {
+        self.first_stream().boundary_capture()
+    }
+}
+
+impl DepletionModel for ParallelStreams {
+    fn name(&self) -> &'static str {
+        "Well between parallel streams"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("distance to first stream", self.distance_to_first_stream),
+            ("distance between streams", self.distance_between_streams),
+            ("specific yield", self.specific_yield),
+            ("transmissivity", self.transmissivity),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), DepletionError> {
+        check_positive("distance between streams", self.distance_between_streams)?;
+        if !(0.0..=self.distance_between_streams).contains(&self.distance_to_first_stream) {
+            return Err(DepletionError::NonPhysicalParameter {
+                name: "distance to first stream",
+                value: self.distance_to_first_stream,
+            });
+        }
+        self.first_stream().validate()
+    }
+
+    /// Calculates the combined depletion fraction of both streams.
+    ///
+    /// # Parameters
+    ///
+    /// * `time`: Time since pumping began (in time units, typically days).
+    fn depletion_fraction(&self, time: f64) -> f64 {
+        self.first_stream().depletion_fraction(time) + self.second_stream().depletion_fraction(time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_well_between_two_streams() {
+        // Well 2000 ft from the first stream and 6000 ft from the second
+        let s: f64 = 0.2;
+        let t: f64 = 261_800.0 / 7.481;
+        let streams = ParallelStreams::new(2000.0, 8000.0, s, t);
+
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0); // acre-feet for month 1
+        let days_per_month = 30.42; // Average days per month
+        let total_months = 120; // 10 years
+
+        let value = calculate_streamflow_depletion_parallel_streams(
+            &pumping_volumes,
+            &streams,
+            1,
+            2,
+            days_per_month,
+            total_months,
+        )
+        .unwrap();
+        assert_eq!(value.len(), 2);
+        let first: f64 = value[&1].iter().map(|(_, v)| v).sum();
+        let second: f64 = value[&2].iter().map(|(_, v)| v).sum();
+
+        // The nearer stream is depleted first and by more
+        assert!(value[&1][0].1 > value[&2].first().map_or(0.0, |v| v.1));
+        assert!(first > second);
+        assert!(first + second <= 100.0 + 1e-6);
+        assert!(first + second > 99.0);
+
+        // the same identifier for both streams would merge their depletion
+        assert_eq!(
+            calculate_streamflow_depletion_parallel_streams(
+                &pumping_volumes,
+                &streams,
+                1,
+                1,
+                days_per_month,
+                total_months,
+            ),
+            Err(DepletionError::InconsistentParameters {
+                name: "stream identifier",
+                value: 1.0,
+                other: "stream identifier",
+                other_value: 1.0
+            })
+        );
+
+        // At steady state the combined depletion equals the pumping rate
+        let time = 1.0e6;
+        let total = streams.depletion_fraction(time);
+        assert!((total - 1.0).abs() < 1e-3);
+        assert!((streams.first_stream().depletion_fraction(time) - 0.75).abs() < 1e-3);
+
+        let aquifer = AquiferParams::new(s, t).unwrap();
+        let metric =
+            ParallelStreams::from_aquifer(Length::meters(609.6), Length::meters(2438.4), &aquifer)
+                .unwrap();
+        assert!((metric.distance_to_first_stream - 2000.0).abs() < 1e-9);
+        assert!((metric.distance_between_streams - 8000.0).abs() < 1e-9);
+        assert!(
+            ParallelStreams::from_aquifer(Length::feet(9000.0), Length::feet(8000.0), &aquifer)
+                .is_err()
+        );
+    }
+}
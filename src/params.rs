@@ -0,0 +1,163 @@
+use crate::error::{DepletionError, check_positive};
+use crate::units::{Storage, Transmissivity, TransmissivityUnit};
+use std::fmt;
+
+/// Validated storage and transmissivity of an aquifer.
+///
+/// The constructor rejects values that are not physically possible, so a model built from an
+/// `AquiferParams` only needs to check its own geometry. Values that are possible but unusual
+/// are accepted and reported by [`AquiferParams::warnings`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AquiferParams {
+    specific_yield: f64,
+    transmissivity: f64,
+}
+
+impl AquiferParams {
+    /// Specific yield above which a warning is given; few unconsolidated materials drain more than this.
+    pub const MAX_TYPICAL_SPECIFIC_YIELD: f64 = 0.35;
+    /// Storativity below which a warning is given.
+    pub const MIN_TYPICAL_STORATIVITY: f64 = 1.0e-6;
+    /// Transmissivity above which a warning is given (in ft²/day).
+    pub const MAX_TYPICAL_TRANSMISSIVITY: f64 = 1.0e6;
+
+    /// Creates validated aquifer parameters.
+    ///
+    /// # Parameters
+    ///
+    /// * `specific_yield`: Storativity or specific yield of the aquifer (dimensionless).
+    /// * `transmissivity`: Transmissivity of the aquifer (in ft²/day).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DepletionError::NonPhysicalParameter`] if the specific yield is not in the range
+    /// (0, 1] or the transmissivity is not finite and greater than zero.
+    pub fn new(specific_yield: f64, transmissivity: f64) -> Result<Self, DepletionError> {
+        check_positive("specific yield", specific_yield)?;
+        if specific_yield > 1.0 {
+            return Err(DepletionError::NonPhysicalParameter {
+                name: "specific yield",
+                value: specific_yield,
+            });
+        }
+        check_positive("transmissivity", transmissivity)?;
+        Ok(AquiferParams {
+            specific_yield,
+            transmissivity,
+        })
+    }
+
+    /// Creates validated aquifer parameters from a transmissivity in any supported unit.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage`: Storativity or specific yield of the aquifer.
+    /// * `transmissivity`: Transmissivity of the aquifer, which is converted to ft²/day.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DepletionError::NonPhysicalParameter`] under the same conditions as [`AquiferParams::new`].
+    pub fn from_units(
+        storage: Storage,
+        transmissivity: Transmissivity,
+    ) -> Result<Self, DepletionError> {
+        AquiferParams::new(
+            storage.0,
+            transmissivity.value(TransmissivityUnit::SquareFeetPerDay),
+        )
+    }
+
+    /// Returns the storativity or specific yield of the aquifer (dimensionless).
+    pub fn specific_yield(&self) -> f64 {
+        self.specific_yield
+    }
+
+    /// Returns the transmissivity of the aquifer (in ft²/day).
+    pub fn transmissivity(&self) -> f64 {
+        self.transmissivity
+    }
+
+    /// Returns the values that are physically possible but unusual enough to be worth checking.
+    pub fn warnings(&self) -> Vec<ParameterWarning> {
+        let mut warnings = Vec::new();
+        if self.specific_yield > Self::MAX_TYPICAL_SPECIFIC_YIELD {
+            warnings.push(ParameterWarning::HighSpecificYield(self.specific_yield));
+        }
+        if self.specific_yield < Self::MIN_TYPICAL_STORATIVITY {
+            warnings.push(ParameterWarning::LowStorativity(self.specific_yield));
+        }
+        if self.transmissivity > Self::MAX_TYPICAL_TRANSMISSIVITY {
+            warnings.push(ParameterWarning::HighTransmissivity(self.transmissivity));
+        }
+        warnings
+    }
+}
+
+/// A parameter value that is physically possible but unusual, which often indicates a unit or data entry error.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParameterWarning {
+    /// The specific yield is above [`AquiferParams::MAX_TYPICAL_SPECIFIC_YIELD`].
+    HighSpecificYield(f64),
+    /// The storativity is below [`AquiferParams::MIN_TYPICAL_STORATIVITY`].
+    LowStorativity(f64),
+    /// The transmissivity is above [`AquiferParams::MAX_TYPICAL_TRANSMISSIVITY`].
+    HighTransmissivity(f64),
+}
+
+impl fmt::Display for ParameterWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterWarning::HighSpecificYield(value) => write!(
+                f,
+                "the specific yield of {value} is higher than {} for most aquifers",
+                AquiferParams::MAX_TYPICAL_SPECIFIC_YIELD
+            ),
+            ParameterWarning::LowStorativity(value) => write!(
+                f,
+                "the storativity of {value} is lower than {} for most aquifers",
+                AquiferParams::MIN_TYPICAL_STORATIVITY
+            ),
+            ParameterWarning::HighTransmissivity(value) => write!(
+                f,
+                "the transmissivity of {value} ft²/day is higher than {} for most aquifers; check the units",
+                AquiferParams::MAX_TYPICAL_TRANSMISSIVITY
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aquifer_params() {
+        let params = AquiferParams::new(0.2, 35_000.0).unwrap();
+        assert_eq!(params.specific_yield(), 0.2);
+        assert!(params.warnings().is_empty());
+
+        assert_eq!(
+            AquiferParams::new(1.5, 35_000.0),
+            Err(DepletionError::NonPhysicalParameter {
+                name: "specific yield",
+                value: 1.5
+            })
+        );
+        assert!(AquiferParams::new(0.2, 0.0).is_err());
+        assert!(AquiferParams::new(0.0, 35_000.0).is_err());
+
+        let params = AquiferParams::new(0.4, 2.0e6).unwrap();
+        assert_eq!(
+            params.warnings(),
+            vec![
+                ParameterWarning::HighSpecificYield(0.4),
+                ParameterWarning::HighTransmissivity(2.0e6)
+            ]
+        );
+
+        let metric =
+            AquiferParams::from_units(Storage(0.2), Transmissivity::square_meters_per_day(3251.6))
+                .unwrap();
+        assert!((metric.transmissivity() - 35_000.0).abs() < 1.0);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::engine::{
+    DepletionOptions, calculate_streamflow_depletion_series, create_results_vector,
+};
+use crate::error::DepletionError;
+use crate::metrics::{MonthlyMetrics, depletion_metrics};
+use crate::model::DepletionModel;
+use crate::series::MonthlySeries;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// The result of a depletion calculation together with everything needed to audit it.
+///
+/// Unlike the `Vec` results, the depletion series covers every month of the horizon, including
+/// months with little or negative depletion, and the flags report where the `Vec` results would
+/// have been cut short.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DepletionResult {
+    /// The name of the model, from [`DepletionModel::name`].
+    pub model: String,
+    /// The name and value of each parameter of the model, from [`DepletionModel::parameters`].
+    pub parameters: Vec<(String, f64)>,
+    /// The horizon, units and method used for the calculation.
+    pub options: DepletionOptions,
+    /// The depletion in each month of the horizon in `options.output_unit`.
+    pub depletion: MonthlySeries,
+    /// The total volume pumped in `options.output_unit`, including any pumping after the horizon.
+    pub total_pumped: f64,
+    /// The total volume depleted from the stream within the horizon in `options.output_unit`.
+    pub total_depleted: f64,
+    /// The pumping not yet depleted from the stream by the end of the horizon,
+    /// `total_pumped - total_depleted`, in `options.output_unit`.
+    pub remaining_depletion: f64,
+    /// The fraction of the pumping eventually captured from the stream, from [`DepletionModel::ultimate_fraction`].
+    pub ultimate_fraction: f64,
+    /// The depletion still owed to the stream after the end of the horizon in `options.output_unit`.
+    ///
+    /// This is a mass balance, `ultimate_fraction * total_pumped - total_depleted`, not an
+    /// evaluation of the response after the horizon, so it gives the total of the tail but not
+    /// when it reaches the stream. It is less than `remaining_depletion` when part of the pumping
+    /// is captured from elsewhere. The `Vec` and daily results have no equivalent.
+    pub tail_depletion: f64,
+    /// Whether the depletion in the last month of the horizon is above `options.reporting.threshold`,
+    /// so the stream is still being depleted when the horizon ends.
+    pub truncated_at_horizon: bool,
+    /// The first month with negative depletion, where the `Vec` results stop.
+    pub negative_depletion: Option<NaiveDate>,
+}
+
+impl DepletionResult {
+    /// Returns the first day of the first month of the horizon.
+    pub fn start_date(&self) -> NaiveDate {
+        self.depletion.start_date()
+    }
+
+    /// Returns the first day after the horizon, or `None` if it is beyond the calendar.
+    pub fn end_date(&self) -> Option<NaiveDate> {
+        self.depletion.date(self.depletion.len())
+    }
+
+    /// Returns the depletion in the same form as [`crate::calculate_streamflow_depletion_with_options`],
+    /// reported as set by `options.reporting`.
+    pub fn to_vec(&self) -> Vec<(NaiveDate, f64)> {
+        create_results_vector(self.depletion.iter(), &self.options.reporting)
+    }
+
+    /// Returns the cumulative depletion, percent of pumping and capture fraction in each month.
+    ///
+    /// # Parameters
+    ///
+    /// * `pumping`: The monthly pumping volumes in `options.pumping_unit` the result was calculated from.
+    ///
+    /// # Returns
+    ///
+    /// The metrics in `options.output_unit` for each month from the start of pumping to the end
+    /// of the horizon, as in [`depletion_metrics`].
+    pub fn metrics(&self, pumping: &MonthlySeries) -> Vec<MonthlyMetrics> {
+        let conversion = self
+            .options
+            .pumping_unit
+            .conversion_to(self.options.output_unit);
+        let pumping = MonthlySeries::new(
+            pumping.start_date(),
+            pumping
+                .values()
+                .iter()
+                .map(|value| value * conversion)
+                .collect(),
+        );
+        depletion_metrics(&pumping, &self.depletion)
+    }
+}
+
+/// Calculates monthly streamflow depletion for any [`DepletionModel`] and records how it was calculated.
+///
+/// # Parameters
+///
+/// * `model`: The depletion model supplying the depletion fraction over time.
+/// * `pumping`: The monthly pumping volumes in `options.pumping_unit`.
+/// * `options`: The horizon, units and method used for the calculation.
+///
+/// # Returns
+///
+/// A [`DepletionResult`] with the dense monthly depletion over the horizon, the model and its
+/// parameters, the options, the total pumped and depleted volumes, the depletion remaining
+/// after the horizon and the truncation flags.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] under the same conditions as [`calculate_streamflow_depletion_series`].
+pub fn calculate_streamflow_depletion_result<M: DepletionModel + ?Sized>(
+    model: &M,
+    pumping: &MonthlySeries,
+    options: &DepletionOptions,
+) -> Result<DepletionResult, DepletionError> {
+    let depletion = calculate_streamflow_depletion_series(model, pumping, options)?;
+    let total_pumped = pumping.values().iter().sum::<f64>()
+        * options.pumping_unit.conversion_to(options.output_unit);
+    let total_depleted: f64 = depletion.values().iter().sum();
+    let truncated_at_horizon = depletion
+        .values()
+        .last()
+        .is_some_and(|depletion| *depletion > options.reporting.threshold);
+    let negative_depletion = depletion
+        .iter()
+        .find(|(_, depletion)| *depletion < 0.0)
+        .map(|(date, _)| date);
+
+    Ok(DepletionResult {
+        model: model.name().to_string(),
+        parameters: model
+            .parameters()
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+        options: *options,
+        depletion,
+        total_pumped,
+        total_depleted,
+        remaining_depletion: total_pumped - total_depleted,
+        ultimate_fraction: model.ultimate_fraction(),
+        tail_depletion: model.ultimate_fraction() * total_pumped - total_depleted,
+        truncated_at_horizon,
+        negative_depletion,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::ConvolutionMethod;
+    use crate::engine::calculate_streamflow_depletion_with_options;
+    use crate::glover_alluvial::{BoundaryType, GloverAlluvial};
+    use crate::glover_infinite::GloverInfinite;
+
+    #[test]
+    fn test_depletion_result() {
+        let mut pumping = MonthlySeries::zeros(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 2);
+        pumping.values_mut().copy_from_slice(&[100.0, 50.0]);
+        let model = GloverInfinite::new(4000.0, 0.2, 261_800.0 / 7.481);
+        let options = DepletionOptions::new(30.42, 24);
+
+        let result = calculate_streamflow_depletion_result(&model, &pumping, &options).unwrap();
+        assert_eq!(result.model, "Glover infinite aquifer");
+        assert_eq!(
+            result.parameters[0],
+            ("distance to well".to_string(), 4000.0)
+        );
+        assert_eq!(result.depletion.len(), 24);
+        assert_eq!(result.end_date(), NaiveDate::from_ymd_opt(2027, 1, 1));
+        assert_eq!(result.total_pumped, 150.0);
+        assert!(result.total_depleted > 0.0 && result.total_depleted < 150.0);
+        assert!(result.truncated_at_horizon);
+        assert_eq!(result.negative_depletion, None);
+
+        let mut map = std::collections::HashMap::new();
+        map.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0);
+        map.insert(NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(), 50.0);
+        assert_eq!(
+            result.to_vec(),
+            calculate_streamflow_depletion_with_options(&model, &map, &options).unwrap()
+        );
+
+        assert_eq!(result.ultimate_fraction, 1.0);
+        assert_eq!(result.remaining_depletion, 150.0 - result.total_depleted);
+        assert!((result.tail_depletion - result.remaining_depletion).abs() < 1e-9);
+
+        let json = serde_json::to_string(&result).unwrap();
+        let value: DepletionResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(value.options, result.options);
+        assert_eq!(value.depletion.start_date(), result.depletion.start_date());
+        for (actual, expected) in value
+            .depletion
+            .values()
+            .iter()
+            .zip(result.depletion.values())
+        {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_tail_depletion_with_shared_capture() {
+        let mut pumping = MonthlySeries::zeros(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 14);
+        pumping.values_mut()[0] = 100.0;
+        pumping.values_mut()[13] = 40.0;
+        let model = GloverAlluvial::new(2000.0, 8000.0, 0.2, 261_800.0 / 7.481)
+            .with_boundary_type(BoundaryType::ConstantHead);
+        let options =
+            DepletionOptions::calendar_months(12).with_method(ConvolutionMethod::MonthlyIntegrated);
+
+        let result = calculate_streamflow_depletion_result(&model, &pumping, &options).unwrap();
+        assert_eq!(result.ultimate_fraction, 0.75);
+        let metrics = result.metrics(&pumping);
+        assert_eq!(metrics.len(), 14);
+        assert!((metrics[11].cumulative_depletion - result.total_depleted).abs() < 1e-9);
+        assert_eq!(metrics[13].capture_fraction, Some(0.0));
+        // pumping after the horizon is all in the tail
+        assert!((result.total_depleted + result.tail_depletion - 0.75 * 140.0).abs() < 1e-9);
+        assert!(result.tail_depletion < result.remaining_depletion);
+    }
+}
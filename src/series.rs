@@ -0,0 +1,597 @@
+use crate::error::DepletionError;
+use crate::utils::add_months;
+use chrono::{Datelike, Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A daily time series stored as a start date and a contiguous array of values.
+///
+/// Element `i` holds the value for the day `i` days after the start date. Days without a value
+/// inside the series are zero.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct DailySeries {
+    start_date: NaiveDate,
+    values: Vec<f64>,
+}
+
+impl DailySeries {
+    pub fn new(start_date: NaiveDate, values: Vec<f64>) -> Self {
+        DailySeries { start_date, values }
+    }
+
+    /// Creates a series of `len` zeros starting at `start_date`.
+    pub fn zeros(start_date: NaiveDate, len: usize) -> Self {
+        DailySeries::new(start_date, vec![0.0; len])
+    }
+
+    /// Returns the date of the first value.
+    pub fn start_date(&self) -> NaiveDate {
+        self.start_date
+    }
+
+    /// Returns the values in date order.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns the values in date order for modification.
+    pub fn values_mut(&mut self) -> &mut [f64] {
+        &mut self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the date of the value at `index`, or `None` if it is beyond the calendar.
+    pub fn date(&self, index: usize) -> Option<NaiveDate> {
+        self.start_date.checked_add_days(Days::new(index as u64))
+    }
+
+    /// Returns the value for `date`, or `None` if the date is outside the series.
+    pub fn get(&self, date: NaiveDate) -> Option<f64> {
+        let index = (date - self.start_date).num_days();
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.values.get(index).copied())
+    }
+
+    /// Adds `value` to the value for `date`, extending the series with zeros to include the date.
+    pub fn add(&mut self, date: NaiveDate, value: f64) {
+        if self.values.is_empty() {
+            self.start_date = date;
+        }
+        let index = (date - self.start_date).num_days();
+        let index = if index < 0 {
+            let mut values = vec![0.0; index.unsigned_abs() as usize];
+            values.append(&mut self.values);
+            self.values = values;
+            self.start_date = date;
+            0
+        } else {
+            index as usize
+        };
+        if index >= self.values.len() {
+            self.values.resize(index + 1, 0.0);
+        }
+        self.values[index] += value;
+    }
+
+    /// Returns an iterator over the dates and values in date order.
+    ///
+    /// Values for days beyond the calendar supported by [`NaiveDate`] have no date and are left out.
+    pub fn iter(&self) -> impl Iterator<Item = (NaiveDate, f64)> + '_ {
+        self.values
+            .iter()
+            .enumerate()
+            .map_while(|(index, value)| Some((self.date(index)?, *value)))
+    }
+
+    /// Returns the series over `len` days starting at `start_date`.
+    ///
+    /// Days outside this series are zero.
+    pub fn window(&self, start_date: NaiveDate, len: usize) -> DailySeries {
+        let mut window = DailySeries::zeros(start_date, len);
+        let offset = (self.start_date - start_date).num_days();
+        for (index, value) in self.values.iter().enumerate() {
+            if let Ok(window_index) = usize::try_from(offset + index as i64)
+                && window_index < len
+            {
+                window.values[window_index] = *value;
+            }
+        }
+        window
+    }
+
+    /// Sums the daily values into the periods of `periods`, replacing its values.
+    ///
+    /// Days outside the periods are ignored.
+    pub fn to_periods(&self, periods: &PeriodSeries) -> PeriodSeries {
+        let mut result = periods.clone();
+        result.values.fill(0.0);
+        for (date, value) in self.iter() {
+            let index = result
+                .boundaries
+                .partition_point(|boundary| *boundary <= date);
+            if index > 0 && index <= result.values.len() {
+                result.values[index - 1] += value;
+            }
+        }
+        result
+    }
+
+    /// Sums the daily values into calendar months.
+    pub fn to_monthly(&self) -> MonthlySeries {
+        let mut monthly = MonthlySeries::default();
+        for (date, value) in self.iter() {
+            monthly.add(date, value);
+        }
+        monthly
+    }
+
+    /// Converts the series into a HashMap keyed by date.
+    pub fn to_map(&self) -> HashMap<NaiveDate, f64> {
+        self.iter().collect()
+    }
+}
+
+impl From<&HashMap<NaiveDate, f64>> for DailySeries {
+    /// Creates a series spanning the earliest to the latest date in the map.
+    fn from(values: &HashMap<NaiveDate, f64>) -> Self {
+        let mut series = DailySeries::default();
+        for (date, value) in values {
+            series.add(*date, *value);
+        }
+        series
+    }
+}
+
+/// A monthly time series stored as a start month and a contiguous array of values.
+///
+/// Months are identified by their first day. Element `i` holds the value for the month `i`
+/// months after the start month. Months without a value inside the series are zero.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MonthlySeries {
+    start_date: NaiveDate,
+    values: Vec<f64>,
+}
+
+impl MonthlySeries {
+    /// Creates a series starting in the month of `start_date`.
+    pub fn new(start_date: NaiveDate, values: Vec<f64>) -> Self {
+        MonthlySeries {
+            start_date: first_of_month(start_date),
+            values,
+        }
+    }
+
+    /// Creates a series of `len` zeros starting in the month of `start_date`.
+    pub fn zeros(start_date: NaiveDate, len: usize) -> Self {
+        MonthlySeries::new(start_date, vec![0.0; len])
+    }
+
+    /// Returns the first day of the first month.
+    pub fn start_date(&self) -> NaiveDate {
+        self.start_date
+    }
+
+    /// Returns the values in date order.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns the values in date order for modification.
+    pub fn values_mut(&mut self) -> &mut [f64] {
+        &mut self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the first day of the month of the value at `index`, or `None` if it is beyond the calendar.
+    pub fn date(&self, index: usize) -> Option<NaiveDate> {
+        i32::try_from(index)
+            .ok()
+            .and_then(|months| add_months(self.start_date, months))
+    }
+
+    /// Returns the number of months from the start month to the month of `date`, which is negative before the start.
+    pub fn month_index(&self, date: NaiveDate) -> i64 {
+        (date.year() - self.start_date.year()) as i64 * 12 + date.month() as i64
+            - self.start_date.month() as i64
+    }
+
+    /// Returns the value for the month of `date`, or `None` if the month is outside the series.
+    pub fn get(&self, date: NaiveDate) -> Option<f64> {
+        usize::try_from(self.month_index(date))
+            .ok()
+            .and_then(|index| self.values.get(index).copied())
+    }
+
+    /// Adds `value` to the value for the month of `date`, extending the series with zeros to include the month.
+    pub fn add(&mut self, date: NaiveDate, value: f64) {
+        if self.values.is_empty() {
+            self.start_date = first_of_month(date);
+        }
+        let index = self.month_index(date);
+        let index = if index < 0 {
+            let mut values = vec![0.0; index.unsigned_abs() as usize];
+            values.append(&mut self.values);
+            self.values = values;
+            self.start_date = first_of_month(date);
+            0
+        } else {
+            index as usize
+        };
+        if index >= self.values.len() {
+            self.values.resize(index + 1, 0.0);
+        }
+        self.values[index] += value;
+    }
+
+    /// Returns the series over `len` months starting in the month of `start_date`.
+    ///
+    /// Months outside this series are zero.
+    pub fn window(&self, start_date: NaiveDate, len: usize) -> MonthlySeries {
+        let mut window = MonthlySeries::zeros(start_date, len);
+        let offset = window.month_index(self.start_date);
+        for (index, value) in self.values.iter().enumerate() {
+            if let Ok(window_index) = usize::try_from(offset + index as i64)
+                && window_index < len
+            {
+                window.values[window_index] = *value;
+            }
+        }
+        window
+    }
+
+    /// Returns an iterator over the first day of each month and its value in date order.
+    ///
+    /// Values for months beyond the calendar supported by [`NaiveDate`] have no date and are left out.
+    pub fn iter(&self) -> impl Iterator<Item = (NaiveDate, f64)> + '_ {
+        self.values
+            .iter()
+            .enumerate()
+            .map_while(|(index, value)| Some((self.date(index)?, *value)))
+    }
+
+    /// Returns the number of days from the start of the series to the start of each month,
+    /// including the end of the last month, so the result has one more element than the series.
+    ///
+    /// Bounds beyond the calendar supported by [`NaiveDate`] are left out, so the result is
+    /// shorter for a series that extends beyond it.
+    pub fn month_bounds(&self) -> Vec<i64> {
+        (0..=self.len())
+            .map_while(|index| self.date(index))
+            .map(|date| (date - self.start_date).num_days())
+            .collect()
+    }
+
+    /// Spreads the value of each month evenly over the days of the month.
+    ///
+    /// Months that end beyond the calendar supported by [`NaiveDate`] are left out.
+    pub fn to_daily(&self) -> DailySeries {
+        let month_bounds = self.month_bounds();
+        let mut daily = DailySeries::zeros(
+            self.start_date,
+            month_bounds.last().copied().unwrap_or(0) as usize,
+        );
+        for (bounds, value) in month_bounds.windows(2).zip(&self.values) {
+            let (month_start, month_end) = (bounds[0] as usize, bounds[1] as usize);
+            daily.values[month_start..month_end].fill(value / (month_end - month_start) as f64);
+        }
+        daily
+    }
+
+    /// Converts the series into a HashMap keyed by the first day of each month.
+    pub fn to_map(&self) -> HashMap<NaiveDate, f64> {
+        self.iter().collect()
+    }
+}
+
+impl From<&HashMap<NaiveDate, f64>> for MonthlySeries {
+    /// Creates a series spanning the earliest to the latest month in the map.
+    ///
+    /// Values for dates in the same month are summed.
+    fn from(values: &HashMap<NaiveDate, f64>) -> Self {
+        let mut series = MonthlySeries::default();
+        for (date, value) in values {
+            series.add(*date, *value);
+        }
+        series
+    }
+}
+
+/// A time series over arbitrary periods, such as weeks, irrigation seasons or model stress periods.
+///
+/// The periods are contiguous: period `i` runs from `boundaries[i]` up to, but not including,
+/// `boundaries[i + 1]`, so there is one more boundary than there are values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeriodSeries {
+    boundaries: Vec<NaiveDate>,
+    values: Vec<f64>,
+}
+
+impl PeriodSeries {
+    /// Creates a series with a value for each period between consecutive boundaries.
+    ///
+    /// # Errors
+    ///
+    /// * [`DepletionError::EmptyInput`] if there are no periods.
+    /// * [`DepletionError::UnorderedPeriods`] if a boundary is not after the previous boundary.
+    /// * [`DepletionError::InconsistentParameters`] if there is not one more boundary than values.
+    pub fn new(boundaries: Vec<NaiveDate>, values: Vec<f64>) -> Result<Self, DepletionError> {
+        if values.is_empty() {
+            return Err(DepletionError::EmptyInput);
+        }
+        if boundaries.len() != values.len() + 1 {
+            return Err(DepletionError::InconsistentParameters {
+                name: "number of period boundaries",
+                value: boundaries.len() as f64,
+                other: "number of periods",
+                other_value: values.len() as f64,
+            });
+        }
+        if let Some(pair) = boundaries.windows(2).find(|pair| pair[1] <= pair[0]) {
+            return Err(DepletionError::UnorderedPeriods(pair[1]));
+        }
+        Ok(PeriodSeries { boundaries, values })
+    }
+
+    /// Creates a series of zeros for each period between consecutive boundaries.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DepletionError`] under the same conditions as [`PeriodSeries::new`].
+    pub fn zeros(boundaries: Vec<NaiveDate>) -> Result<Self, DepletionError> {
+        let len = boundaries.len().saturating_sub(1);
+        PeriodSeries::new(boundaries, vec![0.0; len])
+    }
+
+    /// Creates a series of zeros for `weeks` consecutive weeks starting at `start_date`.
+    ///
+    /// # Errors
+    ///
+    /// * [`DepletionError::EmptyInput`] if `weeks` is zero.
+    /// * [`DepletionError::InvalidDate`] if the weeks extend beyond the calendar.
+    pub fn weekly(start_date: NaiveDate, weeks: usize) -> Result<Self, DepletionError> {
+        let boundaries = (0..=weeks)
+            .map(|week| {
+                (week as u64)
+                    .checked_mul(7)
+                    .and_then(|days| start_date.checked_add_days(Days::new(days)))
+                    .ok_or(DepletionError::InvalidDate(start_date))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        PeriodSeries::zeros(boundaries)
+    }
+
+    /// Returns the period boundaries in date order.
+    pub fn boundaries(&self) -> &[NaiveDate] {
+        &self.boundaries
+    }
+
+    /// Returns the first day of the first period.
+    pub fn start_date(&self) -> NaiveDate {
+        self.boundaries[0]
+    }
+
+    /// Returns the day after the last period.
+    pub fn end_date(&self) -> NaiveDate {
+        self.boundaries[self.values.len()]
+    }
+
+    /// Returns the values in date order.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns the values in date order for modification.
+    pub fn values_mut(&mut self) -> &mut [f64] {
+        &mut self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the number of days from the start of the series to each boundary.
+    pub fn period_bounds(&self) -> Vec<i64> {
+        self.boundaries
+            .iter()
+            .map(|boundary| (*boundary - self.start_date()).num_days())
+            .collect()
+    }
+
+    /// Returns an iterator over the first day of each period and its value in date order.
+    pub fn iter(&self) -> impl Iterator<Item = (NaiveDate, f64)> + '_ {
+        self.boundaries
+            .iter()
+            .copied()
+            .zip(self.values.iter().copied())
+    }
+
+    /// Spreads the value of each period evenly over the days of the period.
+    pub fn to_daily(&self) -> DailySeries {
+        let period_bounds = self.period_bounds();
+        let mut daily = DailySeries::zeros(self.start_date(), period_bounds[self.len()] as usize);
+        for (period, value) in self.values.iter().enumerate() {
+            let (period_start, period_end) = (
+                period_bounds[period] as usize,
+                period_bounds[period + 1] as usize,
+            );
+            daily.values[period_start..period_end].fill(value / (period_end - period_start) as f64);
+        }
+        daily
+    }
+}
+
+impl From<&MonthlySeries> for PeriodSeries {
+    /// Creates a series with a period for each calendar month.
+    ///
+    /// An empty monthly series gives a series with no periods, and months that end beyond the
+    /// calendar supported by [`NaiveDate`] are left out.
+    fn from(series: &MonthlySeries) -> Self {
+        let boundaries: Vec<NaiveDate> = (0..=series.len())
+            .map_while(|index| series.date(index))
+            .collect();
+        let len = boundaries.len().saturating_sub(1);
+        PeriodSeries {
+            boundaries,
+            values: series.values[..len].to_vec(),
+        }
+    }
+}
+
+impl From<&PeriodSeries> for Vec<(NaiveDate, f64)> {
+    fn from(series: &PeriodSeries) -> Self {
+        series.iter().collect()
+    }
+}
+
+impl From<&MonthlySeries> for Vec<(NaiveDate, f64)> {
+    fn from(series: &MonthlySeries) -> Self {
+        series.iter().collect()
+    }
+}
+
+impl From<&DailySeries> for Vec<(NaiveDate, f64)> {
+    fn from(series: &DailySeries) -> Self {
+        series.iter().collect()
+    }
+}
+
+/// Returns the first day of the month of `date`.
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    NaiveDate::from_ymd_opt(date.year(), date.month(), 1).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monthly_series_from_map() {
+        let mut values = HashMap::new();
+        values.insert(NaiveDate::from_ymd_opt(2024, 12, 1).unwrap(), 10.0);
+        values.insert(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(), 5.0);
+        values.insert(NaiveDate::from_ymd_opt(2025, 3, 15).unwrap(), 1.0);
+
+        let series = MonthlySeries::from(&values);
+        assert_eq!(
+            series.start_date(),
+            NaiveDate::from_ymd_opt(2024, 12, 1).unwrap()
+        );
+        assert_eq!(series.values(), &[10.0, 0.0, 0.0, 6.0]);
+        assert_eq!(
+            series.get(NaiveDate::from_ymd_opt(2025, 3, 31).unwrap()),
+            Some(6.0)
+        );
+        assert_eq!(
+            series.get(NaiveDate::from_ymd_opt(2025, 4, 1).unwrap()),
+            None
+        );
+        assert_eq!(series.month_bounds(), vec![0, 31, 62, 90, 121]);
+
+        let window = series.window(NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(), 4);
+        assert_eq!(window.values(), &[0.0, 6.0, 0.0, 0.0]);
+        assert_eq!(
+            MonthlySeries::from(&series.to_map()),
+            MonthlySeries::new(
+                NaiveDate::from_ymd_opt(2024, 12, 1).unwrap(),
+                series.values().to_vec()
+            )
+        );
+    }
+
+    #[test]
+    fn test_period_series() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+        // an irrigation season and the rest of the year
+        let seasons = PeriodSeries::new(
+            vec![date(1, 1), date(4, 15), date(10, 15), date(12, 31)],
+            vec![0.0, 183.0, 0.0],
+        )
+        .unwrap();
+        let daily = seasons.to_daily();
+        assert_eq!(daily.get(date(4, 14)), Some(0.0));
+        assert_eq!(daily.get(date(4, 15)), Some(1.0));
+        assert_eq!(daily.get(date(10, 15)), Some(0.0));
+
+        let weeks = daily.to_periods(&PeriodSeries::weekly(date(4, 10), 2).unwrap());
+        assert_eq!(weeks.values(), &[2.0, 7.0]);
+        assert_eq!(weeks.end_date(), date(4, 24));
+
+        assert_eq!(
+            PeriodSeries::new(vec![date(2, 1), date(1, 1)], vec![1.0]),
+            Err(DepletionError::UnorderedPeriods(date(1, 1)))
+        );
+        assert!(PeriodSeries::new(vec![date(1, 1)], vec![1.0]).is_err());
+        assert_eq!(
+            PeriodSeries::zeros(vec![date(1, 1)]),
+            Err(DepletionError::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn test_daily_series_to_monthly() {
+        let mut series = DailySeries::default();
+        series.add(NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(), 1.0);
+        series.add(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(), 2.0);
+        series.add(NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(), 3.0);
+        assert_eq!(
+            series.start_date(),
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()
+        );
+        assert_eq!(series.len(), 29);
+
+        let monthly = series.to_monthly();
+        assert_eq!(
+            monthly.start_date(),
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
+        );
+        assert_eq!(monthly.values(), &[2.0, 4.0]);
+
+        let daily = MonthlySeries::new(monthly.start_date(), vec![31.0, 56.0]).to_daily();
+        assert_eq!(daily.len(), 59);
+        assert_eq!(
+            daily.get(NaiveDate::from_ymd_opt(2025, 2, 28).unwrap()),
+            Some(2.0)
+        );
+        let window = daily.window(NaiveDate::from_ymd_opt(2025, 2, 27).unwrap(), 4);
+        assert_eq!(window.values(), &[2.0, 2.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_series_at_end_of_calendar() {
+        // the last month of the calendar has no following month to end it
+        let monthly = MonthlySeries::new(NaiveDate::MAX, vec![31.0, 1.0]);
+        assert_eq!(monthly.date(0), Some(first_of_month(NaiveDate::MAX)));
+        assert_eq!(monthly.date(1), None);
+        assert_eq!(monthly.iter().count(), 1);
+        assert_eq!(monthly.month_bounds(), vec![0]);
+        assert!(monthly.to_daily().is_empty());
+        assert!(PeriodSeries::from(&monthly).is_empty());
+
+        let daily = DailySeries::new(NaiveDate::MAX, vec![1.0, 2.0]);
+        assert_eq!(daily.date(1), None);
+        assert_eq!(daily.to_monthly().values(), &[1.0]);
+        assert_eq!(
+            PeriodSeries::weekly(NaiveDate::MAX, 1),
+            Err(DepletionError::InvalidDate(NaiveDate::MAX))
+        );
+    }
+}
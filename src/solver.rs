@@ -0,0 +1,193 @@
+//! Time at which depletion under constant pumping reaches a target fraction of the pumping.
+//!
+//! This answers questions such as "when does depletion from this well reach 28% of the pumping
+//! rate?" for any [`DepletionModel`] by finding the root of its depletion fraction in time.
+
+use crate::error::{DepletionError, check_positive};
+use crate::model::DepletionModel;
+use crate::units::{Rate, RateUnit};
+use serde::{Deserialize, Serialize};
+
+/// The longest time searched for the target (in days), about 270,000 years.
+const MAX_TIME: f64 = 1.0e8;
+/// The relative tolerance on the time found.
+const TIME_TOLERANCE: f64 = 1.0e-10;
+
+/// The measure of depletion compared with the target fraction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DepletionMeasure {
+    /// The depletion rate as a fraction of the pumping rate, [`DepletionModel::depletion_fraction`].
+    #[default]
+    Rate,
+    /// The volume depleted as a fraction of the volume pumped since pumping began,
+    /// [`DepletionModel::depletion_volume`] divided by the time. The stream depletion factor is
+    /// the time at which this reaches about 28%.
+    Volume,
+}
+
+impl DepletionMeasure {
+    /// Returns the measure of depletion at `time` (in days) under constant pumping.
+    fn fraction<M: DepletionModel + ?Sized>(&self, model: &M, time: f64) -> f64 {
+        match self {
+            DepletionMeasure::Rate => model.depletion_fraction(time),
+            DepletionMeasure::Volume => model.depletion_volume(time) / time,
+        }
+    }
+}
+
+/// Calculates the time at which depletion from constant pumping reaches a fraction of the pumping.
+///
+/// The time is bracketed by doubling from one day and then found by bisection, which assumes the
+/// measure of depletion increases with time, as it does for every model in this crate.
+///
+/// # Parameters
+///
+/// * `model`: The depletion model supplying the depletion fraction over time.
+/// * `target`: The fraction of the pumping to reach, between 0 and 1.
+/// * `measure`: Whether the target applies to the depletion rate or the cumulative volume.
+///
+/// # Returns
+///
+/// The time since pumping began (in days) at which the measure of depletion reaches `target`.
+///
+/// # Errors
+///
+/// Returns [`DepletionError::NonPhysicalParameter`] if the target is not in the range (0, 1] or
+/// the model parameters are invalid, and [`DepletionError::TargetNotReached`] if the target is not
+/// below [`DepletionModel::ultimate_fraction`], such as any target for alluvial boundary capture
+/// behind a no-flow boundary, or is not reached within about 270,000 years.
+pub fn time_to_depletion_fraction<M: DepletionModel + ?Sized>(
+    model: &M,
+    target: f64,
+    measure: DepletionMeasure,
+) -> Result<f64, DepletionError> {
+    model.validate()?;
+    if !(target > 0.0 && target <= 1.0) {
+        return Err(DepletionError::NonPhysicalParameter {
+            name: "target fraction",
+            value: target,
+        });
+    }
+    let not_reached = DepletionError::TargetNotReached {
+        target,
+        ultimate_fraction: model.ultimate_fraction(),
+    };
+    if target >= model.ultimate_fraction() {
+        return Err(not_reached);
+    }
+
+    let mut lower = 0.0;
+    let mut upper = 1.0;
+    while measure.fraction(model, upper) < target {
+        lower = upper;
+        upper *= 2.0;
+        if upper > MAX_TIME {
+            return Err(not_reached);
+        }
+    }
+    while upper - lower > TIME_TOLERANCE * upper {
+        let time = 0.5 * (lower + upper);
+        if measure.fraction(model, time) < target {
+            lower = time;
+        } else {
+            upper = time;
+        }
+    }
+    Ok(0.5 * (lower + upper))
+}
+
+/// Calculates the time at which constant pumping at `pumping_rate` depletes the stream at `depletion_rate`.
+///
+/// This is [`time_to_depletion_fraction`] for the rate measure, with the target given as a flow
+/// rate in any unit, such as "when does pumping 2 cfs deplete the stream by 0.5 cfs?".
+///
+/// # Parameters
+///
+/// * `model`: The depletion model supplying the depletion fraction over time.
+/// * `pumping_rate`: The constant pumping rate.
+/// * `depletion_rate`: The depletion rate to reach.
+///
+/// # Returns
+///
+/// The time since pumping began (in days) at which the depletion rate reaches `depletion_rate`.
+///
+/// # Errors
+///
+/// Returns [`DepletionError::NonPhysicalParameter`] if the pumping rate is not greater than zero,
+/// and otherwise the errors of [`time_to_depletion_fraction`], so a depletion rate above the
+/// pumping rate is reported as a target fraction above 1.
+pub fn time_to_depletion_rate<M: DepletionModel + ?Sized>(
+    model: &M,
+    pumping_rate: Rate,
+    depletion_rate: Rate,
+) -> Result<f64, DepletionError> {
+    let pumping = pumping_rate.value(RateUnit::CubicFeetPerDay);
+    check_positive("pumping rate", pumping)?;
+    time_to_depletion_fraction(
+        model,
+        depletion_rate.value(RateUnit::CubicFeetPerDay) / pumping,
+        DepletionMeasure::Rate,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glover_alluvial::{BoundaryType, GloverAlluvial};
+    use crate::glover_infinite::GloverInfinite;
+    use crate::sdf::StreamDepletionFactor;
+    use scirs2_special::erfc;
+
+    #[test]
+    fn test_time_to_depletion_fraction() {
+        let sdf = StreamDepletionFactor::new(200.0);
+        let time = time_to_depletion_fraction(&sdf, erfc(0.5), DepletionMeasure::Rate).unwrap();
+        assert!((time - 200.0).abs() < 1e-6);
+        // the volume fraction reaches about 28% at the stream depletion factor
+        let time = time_to_depletion_fraction(&sdf, 0.28, DepletionMeasure::Volume).unwrap();
+        assert!((time - 200.0).abs() < 2.0);
+
+        let glover = GloverInfinite::new(1000.0, 0.2, 35_000.0);
+        for target in [0.1, 0.28, 0.5] {
+            let time = time_to_depletion_fraction(&glover, target, DepletionMeasure::Rate).unwrap();
+            assert!((glover.depletion_fraction(time) - target).abs() < 1e-9);
+        }
+
+        let time = time_to_depletion_rate(
+            &glover,
+            Rate::new(2.0, RateUnit::Cfs),
+            Rate::new(448.831_17 / 2.0, RateUnit::GallonsPerMinute),
+        )
+        .unwrap();
+        assert!((glover.depletion_fraction(time) - 0.25).abs() < 1e-6);
+
+        assert_eq!(
+            time_to_depletion_fraction(&glover, 1.5, DepletionMeasure::Rate),
+            Err(DepletionError::NonPhysicalParameter {
+                name: "target fraction",
+                value: 1.5
+            })
+        );
+    }
+
+    #[test]
+    fn test_target_not_reached() {
+        let alluvial = GloverAlluvial::new(2000.0, 8000.0, 0.2, 35_000.0)
+            .with_boundary_type(BoundaryType::ConstantHead);
+        let time = time_to_depletion_fraction(&alluvial, 0.5, DepletionMeasure::Volume).unwrap();
+        assert!((alluvial.depletion_volume(time) / time - 0.5).abs() < 1e-9);
+        assert_eq!(
+            time_to_depletion_fraction(&alluvial, 0.8, DepletionMeasure::Rate),
+            Err(DepletionError::TargetNotReached {
+                target: 0.8,
+                ultimate_fraction: 0.75
+            })
+        );
+
+        let capture = GloverAlluvial::new(2000.0, 8000.0, 0.2, 35_000.0).boundary_capture();
+        assert!(matches!(
+            time_to_depletion_fraction(&capture, 0.1, DepletionMeasure::Rate),
+            Err(DepletionError::TargetNotReached { .. })
+        ));
+    }
+}
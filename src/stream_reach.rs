@@ -0,0 +1,315 @@
+use crate::engine::calculate_streamflow_depletion;
+use crate::error::{DepletionError, check_positive};
+use crate::glover_infinite::stream_reach_depletion_fraction;
+use crate::model::DepletionModel;
+use crate::params::AquiferParams;
+use crate::units::{Length, LengthUnit};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// Calculates the streamflow depletion captured by one reach of an infinite straight stream.
+///
+/// This function computes the monthly share of the Glover depletion that enters the stream
+/// between two points, such as the reach of a river within a water district. The rest of the
+/// depletion enters the stream outside the reach, so depletion of the reach approaches a fraction
+/// of the pumping below one; see [`StreamReach`].
+///
+/// # Parameters
+///
+/// * `pumping_volumes_monthly`: A HashMap containing monthly pumping volumes in acre-ft/month, keyed by date.
+/// * `distance_to_well`: The perpendicular distance from the well to the stream line in feet.
+/// * `reach_start`: The start of the reach in feet, measured along the stream from the point nearest
+///   the well. Use `f64::NEG_INFINITY` for a reach that extends upstream without end.
+/// * `reach_end`: The end of the reach in feet, measured the same way. Use `f64::INFINITY` for a
+///   reach that extends downstream without end.
+/// * `specific_yield`: The specific yield of the aquifer (dimensionless).
+/// * `transmissivity`: The transmissivity of the aquifer in ft²/day.
+/// * `days_per_month`: The average number of days per month used in calculations.
+/// * `total_months`: The total number of months to calculate depletion for.
+///
+/// # Returns
+///
+/// A Vec of tuples, where each tuple contains a date and the corresponding monthly streamflow depletion in acre-ft/month.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] if the pumping, parameters or horizon are invalid; see
+/// [`calculate_streamflow_depletion`].
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_streamflow_depletion_stream_reach(
+    pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in acre-ft / month
+    distance_to_well: f64,
+    reach_start: f64,
+    reach_end: f64,
+    specific_yield: f64,
+    transmissivity: f64,
+    days_per_month: f64,
+    total_months: usize,
+) -> Result<Vec<(NaiveDate, f64)>, DepletionError> {
+    let model = StreamReach::new(
+        distance_to_well,
+        reach_start,
+        reach_end,
+        specific_yield,
+        transmissivity,
+    );
+    calculate_streamflow_depletion(
+        &model,
+        pumping_volumes_monthly,
+        days_per_month,
+        total_months,
+    )
+}
+
+/// The depletion captured by one reach of an infinite straight stream.
+///
+/// The Glover solution holds the head constant along the whole stream line. This model partitions
+/// that depletion along the stream and counts only the flow from the well and its image well into
+/// the reach between `reach_start` and `reach_end`. The remainder of the pumping is captured by
+/// the stream outside the reach, not by aquifer storage, so the reaches of a stream together give
+/// the Glover depletion. It is not a model of a stream that ends near the well.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StreamReach {
+    /// Perpendicular distance from the well to the stream line (in length units, typically feet).
+    pub distance_to_well: f64,
+    /// Start of the reach, measured along the stream from the point nearest the well (in length units, typically feet). Can be `f64::NEG_INFINITY`.
+    pub reach_start: f64,
+    /// End of the reach, measured the same way (in length units, typically feet). Can be `f64::INFINITY`.
+    pub reach_end: f64,
+    /// Storativity or specific yield of the aquifer (dimensionless).
+    pub specific_yield: f64,
+    /// Transmissivity of the aquifer (in length²/time units, typically ft²/day).
+    pub transmissivity: f64,
+}
+
+impl StreamReach {
+    pub fn new(
+        distance_to_well: f64,
+        reach_start: f64,
+        reach_end: f64,
+        specific_yield: f64,
+        transmissivity: f64,
+    ) -> Self {
+        StreamReach {
+            distance_to_well,
+            reach_start,
+            reach_end,
+            specific_yield,
+            transmissivity,
+        }
+    }
+
+    /// Creates the model from validated aquifer parameters.
+    ///
+    /// # Parameters
+    ///
+    /// * `distance_to_well`: Perpendicular distance from the well to the stream line.
+    /// * `reach_start`: Start of the reach, measured along the stream from the point nearest the well. Can be infinite.
+    /// * `reach_end`: End of the reach, measured the same way. Can be infinite.
+    /// * `aquifer`: The validated storage and transmissivity of the aquifer.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DepletionError`] if the distance is not greater than zero, an end of the reach
+    /// is NaN or the end is before the start.
+    pub fn from_aquifer(
+        distance_to_well: Length,
+        reach_start: Length,
+        reach_end: Length,
+        aquifer: &AquiferParams,
+    ) -> Result<Self, DepletionError> {
+        let model = StreamReach::new(
+            distance_to_well.value(LengthUnit::Feet),
+            reach_start.value(LengthUnit::Feet),
+            reach_end.value(LengthUnit::Feet),
+            aquifer.specific_yield(),
+            aquifer.transmissivity(),
+        );
+        model.validate()?;
+        Ok(model)
+    }
+
+    /// Returns the depletion fraction that is approached at steady state.
+    ///
+    /// This is the angle the reach subtends at the well divided by `π`, so a reach that begins
+    /// opposite the well and continues downstream approaches half of the pumping rate.
+    pub fn steady_state_fraction(&self) -> f64 {
+        ((self.reach_end / self.distance_to_well).atan()
+            - (self.reach_start / self.distance_to_well).atan())
+            / PI
+    }
+}
+
+impl DepletionModel for StreamReach {
+    fn name(&self) -> &'static str {
+        "Glover stream reach"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("distance to well", self.distance_to_well),
+            ("reach start", self.reach_start),
+            ("reach end", self.reach_end),
+            ("specific yield", self.specific_yield),
+            ("transmissivity", self.transmissivity),
+        ]
+    }
+
+    fn ultimate_fraction(&self) -> f64 {
+        self.steady_state_fraction()
+    }
+
+    fn validate(&self) -> Result<(), DepletionError> {
+        // the angle the reach subtends is undefined for a well on the stream line
+        check_positive("distance to well", self.distance_to_well)?;
+        AquiferParams::new(self.specific_yield, self.transmissivity)?;
+        if self.reach_start.is_nan() {
+            return Err(DepletionError::NonPhysicalParameter {
+                name: "reach start",
+                value: self.reach_start,
+            });
+        }
+        if self.reach_end.is_nan() || self.reach_end < self.reach_start {
+            return Err(DepletionError::NonPhysicalParameter {
+                name: "reach end",
+                value: self.reach_end,
+            });
+        }
+        Ok(())
+    }
+
+    /// Calculates the fraction of the pumping captured by the reach.
+    ///
+    /// # Parameters
+    ///
+    /// * `time`: Time since pumping began (in time units, typically days).
+    ///
+    /// # Returns
+    ///
+    /// Returns the depletion fraction as a `f64`, representing the proportion of pumping
+    /// that has been captured from the reach at the given time.
+    fn depletion_fraction(&self, time: f64) -> f64 {
+        stream_reach_depletion_fraction(
+            self.distance_to_well,
+            self.reach_start,
+            self.reach_end,
+            self.specific_yield,
+            self.transmissivity,
+            time,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glover_infinite::GloverInfinite;
+
+    #[test]
+    fn test_reach_beginning_opposite_well() {
+        let d: f64 = 4000.0;
+        let s: f64 = 0.2;
+        let t: f64 = 261_800.0 / 7.481;
+        let downstream = StreamReach::new(d, 0.0, f64::INFINITY, s, t);
+        let upstream = StreamReach::new(d, f64::NEG_INFINITY, 0.0, s, t);
+        let glover = GloverInfinite::new(d, s, t);
+
+        assert!((downstream.steady_state_fraction() - 0.5).abs() < 1e-12);
+        assert!((upstream.steady_state_fraction() - 0.5).abs() < 1e-12);
+        for time in [30.0, 365.0, 3650.0] {
+            let fraction = downstream.depletion_fraction(time);
+            assert!((fraction - 0.5 * glover.depletion_fraction(time)).abs() < 1e-6);
+            // the two halves of the stream together give the Glover depletion
+            let total = fraction + upstream.depletion_fraction(time);
+            assert!((total - glover.depletion_fraction(time)).abs() < 1e-6);
+        }
+        assert!(downstream.depletion_fraction(1.0e9) < 0.5);
+        assert!(downstream.depletion_fraction(1.0e9) > 0.499);
+
+        let whole = StreamReach::new(d, f64::NEG_INFINITY, f64::INFINITY, s, t);
+        assert_eq!(whole.validate(), Ok(()));
+        assert_eq!(whole.ultimate_fraction(), 1.0);
+        assert!((whole.depletion_fraction(365.0) - glover.depletion_fraction(365.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_stream_reach_depletion() {
+        // A 2000 ft reach centred on the point nearest the well
+        let d: f64 = 1000.0;
+        let s: f64 = 0.2;
+        let t: f64 = 261_800.0 / 7.481;
+        let reach = StreamReach::new(d, -1000.0, 1000.0, s, t);
+        assert!((reach.steady_state_fraction() - 0.5).abs() < 1e-12);
+
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0); // acre-feet for month 1
+        let days_per_month = 30.42; // Average days per month
+        let total_months = 120; // 10 years
+
+        let value = calculate_streamflow_depletion_stream_reach(
+            &pumping_volumes,
+            d,
+            -1000.0,
+            1000.0,
+            s,
+            t,
+            days_per_month,
+            total_months,
+        )
+        .unwrap();
+        let infinite = crate::calculate_streamflow_depletion_infinite(
+            &pumping_volumes,
+            d,
+            s,
+            t,
+            days_per_month,
+            total_months,
+        )
+        .unwrap();
+
+        let total: f64 = value.iter().map(|(_, v)| v).sum();
+        let infinite_total: f64 = infinite.iter().map(|(_, v)| v).sum();
+        assert!(value[0].1 < infinite[0].1);
+        assert!(total < 50.0 + 1e-6);
+        assert!(total < infinite_total);
+    }
+
+    #[test]
+    fn test_invalid_geometry_is_rejected() {
+        let reach = StreamReach::new(1000.0, f64::NAN, 1000.0, 0.2, 35_000.0);
+        assert!(matches!(
+            reach.validate(),
+            Err(DepletionError::NonPhysicalParameter {
+                name: "reach start",
+                ..
+            })
+        ));
+        let reach = StreamReach::new(1000.0, 1000.0, -1000.0, 0.2, 35_000.0);
+        assert!(matches!(
+            reach.validate(),
+            Err(DepletionError::NonPhysicalParameter {
+                name: "reach end",
+                ..
+            })
+        ));
+        let aquifer = AquiferParams::new(0.2, 35_000.0).unwrap();
+        let reach = StreamReach::from_aquifer(
+            Length::meters(304.8),
+            Length::meters(f64::NEG_INFINITY),
+            Length::meters(0.0),
+            &aquifer,
+        )
+        .unwrap();
+        assert!((reach.distance_to_well - 1000.0).abs() < 1e-9);
+        assert_eq!(reach.reach_start, f64::NEG_INFINITY);
+        let reach = StreamReach::new(0.0, 0.0, 1000.0, 0.2, 35_000.0);
+        assert!(matches!(
+            reach.validate(),
+            Err(DepletionError::NonPhysicalParameter {
+                name: "distance to well",
+                ..
+            })
+        ));
+    }
+}
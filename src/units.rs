@@ -0,0 +1,310 @@
+//! Unit-aware quantities for aquifer parameters, pumping and depletion.
+//!
+//! Each quantity stores its value in the US customary unit used by the analytical solutions
+//! (feet, ft²/day and ft³), so values given in any supported unit are converted once when they
+//! are created and can be read back in any other unit.
+
+use serde::{Deserialize, Serialize};
+
+/// Feet in one metre.
+const FEET_PER_METER: f64 = 1.0 / 0.3048;
+/// US gallons in one cubic foot.
+const GALLONS_PER_CUBIC_FOOT: f64 = 1728.0 / 231.0;
+/// Cubic feet in one acre-foot.
+const CUBIC_FEET_PER_ACRE_FOOT: f64 = 43_560.0;
+/// Seconds in one day.
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// A unit of length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LengthUnit {
+    #[default]
+    Feet,
+    Meters,
+}
+
+impl LengthUnit {
+    /// Returns the number of feet in one of this unit.
+    pub fn feet(&self) -> f64 {
+        match self {
+            LengthUnit::Feet => 1.0,
+            LengthUnit::Meters => FEET_PER_METER,
+        }
+    }
+}
+
+/// A length, such as the distance from a well to a stream.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Length {
+    feet: f64,
+}
+
+impl Length {
+    pub fn new(value: f64, unit: LengthUnit) -> Self {
+        Length {
+            feet: value * unit.feet(),
+        }
+    }
+
+    pub fn feet(value: f64) -> Self {
+        Length::new(value, LengthUnit::Feet)
+    }
+
+    pub fn meters(value: f64) -> Self {
+        Length::new(value, LengthUnit::Meters)
+    }
+
+    /// Returns the length in `unit`.
+    pub fn value(&self, unit: LengthUnit) -> f64 {
+        self.feet / unit.feet()
+    }
+}
+
+/// A unit of transmissivity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TransmissivityUnit {
+    #[default]
+    SquareFeetPerDay,
+    SquareMetersPerDay,
+    /// US gallons per day per foot of aquifer width, common in well test reports.
+    GallonsPerDayPerFoot,
+}
+
+impl TransmissivityUnit {
+    /// Returns the number of ft²/day in one of this unit.
+    pub fn square_feet_per_day(&self) -> f64 {
+        match self {
+            TransmissivityUnit::SquareFeetPerDay => 1.0,
+            TransmissivityUnit::SquareMetersPerDay => FEET_PER_METER * FEET_PER_METER,
+            TransmissivityUnit::GallonsPerDayPerFoot => 1.0 / GALLONS_PER_CUBIC_FOOT,
+        }
+    }
+}
+
+/// The transmissivity of an aquifer.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Transmissivity {
+    square_feet_per_day: f64,
+}
+
+impl Transmissivity {
+    pub fn new(value: f64, unit: TransmissivityUnit) -> Self {
+        Transmissivity {
+            square_feet_per_day: value * unit.square_feet_per_day(),
+        }
+    }
+
+    pub fn square_feet_per_day(value: f64) -> Self {
+        Transmissivity::new(value, TransmissivityUnit::SquareFeetPerDay)
+    }
+
+    pub fn square_meters_per_day(value: f64) -> Self {
+        Transmissivity::new(value, TransmissivityUnit::SquareMetersPerDay)
+    }
+
+    pub fn gallons_per_day_per_foot(value: f64) -> Self {
+        Transmissivity::new(value, TransmissivityUnit::GallonsPerDayPerFoot)
+    }
+
+    /// Returns the transmissivity in `unit`.
+    pub fn value(&self, unit: TransmissivityUnit) -> f64 {
+        self.square_feet_per_day / unit.square_feet_per_day()
+    }
+}
+
+/// A unit of hydraulic conductivity, a length per day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ConductivityUnit {
+    #[default]
+    FeetPerDay,
+    MetersPerDay,
+}
+
+impl ConductivityUnit {
+    /// Returns the number of ft/day in one of this unit.
+    pub fn feet_per_day(&self) -> f64 {
+        match self {
+            ConductivityUnit::FeetPerDay => 1.0,
+            ConductivityUnit::MetersPerDay => FEET_PER_METER,
+        }
+    }
+}
+
+/// A hydraulic conductivity, or a streambed conductance, which has the same units.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Conductivity {
+    feet_per_day: f64,
+}
+
+impl Conductivity {
+    pub fn new(value: f64, unit: ConductivityUnit) -> Self {
+        Conductivity {
+            feet_per_day: value * unit.feet_per_day(),
+        }
+    }
+
+    pub fn feet_per_day(value: f64) -> Self {
+        Conductivity::new(value, ConductivityUnit::FeetPerDay)
+    }
+
+    pub fn meters_per_day(value: f64) -> Self {
+        Conductivity::new(value, ConductivityUnit::MetersPerDay)
+    }
+
+    /// Returns the conductivity in `unit`.
+    pub fn value(&self, unit: ConductivityUnit) -> f64 {
+        self.feet_per_day / unit.feet_per_day()
+    }
+}
+
+/// The storativity or specific yield of an aquifer, which is dimensionless.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Storage(pub f64);
+
+/// A unit of volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum VolumeUnit {
+    #[default]
+    AcreFeet,
+    CubicFeet,
+    CubicMeters,
+    /// The volume of one cubic foot per second flowing for a day.
+    CfsDays,
+}
+
+impl VolumeUnit {
+    /// Returns the number of ft³ in one of this unit.
+    pub fn cubic_feet(&self) -> f64 {
+        match self {
+            VolumeUnit::AcreFeet => CUBIC_FEET_PER_ACRE_FOOT,
+            VolumeUnit::CubicFeet => 1.0,
+            VolumeUnit::CubicMeters => FEET_PER_METER.powi(3),
+            VolumeUnit::CfsDays => SECONDS_PER_DAY,
+        }
+    }
+
+    /// Returns the factor that converts a volume in this unit to `unit`.
+    pub fn conversion_to(&self, unit: VolumeUnit) -> f64 {
+        if *self == unit {
+            1.0
+        } else {
+            self.cubic_feet() / unit.cubic_feet()
+        }
+    }
+}
+
+/// A volume of water, such as a month of pumping.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Volume {
+    cubic_feet: f64,
+}
+
+impl Volume {
+    pub fn new(value: f64, unit: VolumeUnit) -> Self {
+        Volume {
+            cubic_feet: value * unit.cubic_feet(),
+        }
+    }
+
+    pub fn acre_feet(value: f64) -> Self {
+        Volume::new(value, VolumeUnit::AcreFeet)
+    }
+
+    pub fn cubic_meters(value: f64) -> Self {
+        Volume::new(value, VolumeUnit::CubicMeters)
+    }
+
+    /// Returns the volume in `unit`.
+    pub fn value(&self, unit: VolumeUnit) -> f64 {
+        self.cubic_feet / unit.cubic_feet()
+    }
+}
+
+/// A unit of flow rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RateUnit {
+    #[default]
+    CubicFeetPerDay,
+    CubicMetersPerDay,
+    AcreFeetPerDay,
+    /// Cubic feet per second.
+    Cfs,
+    /// US gallons per minute.
+    GallonsPerMinute,
+}
+
+impl RateUnit {
+    /// Returns the number of ft³/day in one of this unit.
+    pub fn cubic_feet_per_day(&self) -> f64 {
+        match self {
+            RateUnit::CubicFeetPerDay => 1.0,
+            RateUnit::CubicMetersPerDay => VolumeUnit::CubicMeters.cubic_feet(),
+            RateUnit::AcreFeetPerDay => CUBIC_FEET_PER_ACRE_FOOT,
+            RateUnit::Cfs => SECONDS_PER_DAY,
+            RateUnit::GallonsPerMinute => 1440.0 / GALLONS_PER_CUBIC_FOOT,
+        }
+    }
+}
+
+/// A flow rate, such as a pumping or depletion rate.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Rate {
+    cubic_feet_per_day: f64,
+}
+
+impl Rate {
+    pub fn new(value: f64, unit: RateUnit) -> Self {
+        Rate {
+            cubic_feet_per_day: value * unit.cubic_feet_per_day(),
+        }
+    }
+
+    /// Returns the rate in `unit`.
+    pub fn value(&self, unit: RateUnit) -> f64 {
+        self.cubic_feet_per_day / unit.cubic_feet_per_day()
+    }
+
+    /// Returns the volume pumped at this rate over `days`.
+    pub fn volume_over(&self, days: f64) -> Volume {
+        Volume::new(self.cubic_feet_per_day * days, VolumeUnit::CubicFeet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_conversions() {
+        assert!((Length::meters(1000.0).value(LengthUnit::Feet) - 3_280.839_895).abs() < 1e-6);
+        assert_eq!(Length::feet(4000.0).value(LengthUnit::Feet), 4000.0);
+
+        // the tests convert gpd/ft with 7.481 gallons per cubic foot
+        let t = Transmissivity::gallons_per_day_per_foot(261_800.0);
+        assert!((t.value(TransmissivityUnit::SquareFeetPerDay) - 261_800.0 / 7.481).abs() < 5.0);
+        let t = Transmissivity::square_meters_per_day(1000.0);
+        assert!((t.value(TransmissivityUnit::SquareFeetPerDay) - 10_763.910_4).abs() < 1e-3);
+
+        assert!(
+            (Volume::acre_feet(1.0).value(VolumeUnit::CubicMeters) - 1_233.481_84).abs() < 1e-5
+        );
+        assert!((VolumeUnit::CfsDays.conversion_to(VolumeUnit::AcreFeet) - 1.983_471).abs() < 1e-6);
+        assert_eq!(
+            VolumeUnit::AcreFeet.conversion_to(VolumeUnit::AcreFeet),
+            1.0
+        );
+
+        assert!(
+            (Conductivity::meters_per_day(1.0).value(ConductivityUnit::FeetPerDay) - 3.280_839_9)
+                .abs()
+                < 1e-6
+        );
+
+        let rate = Rate::new(1.0, RateUnit::Cfs);
+        assert!((rate.volume_over(1.0).value(VolumeUnit::CfsDays) - 1.0).abs() < 1e-12);
+        assert!(
+            (Rate::new(448.831_17, RateUnit::GallonsPerMinute).value(RateUnit::Cfs) - 1.0).abs()
+                < 1e-6
+        );
+    }
+}
@@ -1,10 +1,11 @@
-use chrono::{Months, NaiveDate};
+use crate::engine::{ReportingOptions, create_results_vector};
+use crate::series::MonthlySeries;
+use chrono::NaiveDate;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::ops::Add;
 
-pub type LaggedUrfByDate = HashMap<NaiveDate, f64>;
+pub type LaggedUrfByDate = MonthlySeries;
 pub type LaggedUrfResult = HashMap<i32, LaggedUrfByDate>;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
@@ -27,40 +28,42 @@ impl UrfValue {
 /// Computes the lagged usage rate factor (URF) for different reaches over time.
 ///
 /// This function takes a vector of `UrfValue` and a usage map and calculates the lagged URF
-/// for each reach over the given usage dates. The result is a nested `HashMap` where the outer
-/// key is the reach identifier, and the inner key-value pairs represent the date and the corresponding
-/// lagged URF value.
+/// for each reach over the given usage dates. The result is a `HashMap` where the key is the
+/// reach identifier, and the value is a monthly series of the corresponding lagged URF values.
 ///
 /// # Parameters
 ///
 /// - `urf`: A vector of `UrfValue` structs, each containing the month, reach, and URF value.
-/// - `usage`: A reference to a `HashMap` where the keys are `NaiveDate` objects representing the usage dates,
-///   and the values are the usage amounts for those dates.
+/// - `usage`: A reference to a `HashMap` where the keys are `NaiveDate` objects representing the usage months,
+///   and the values are the usage amounts for those months.
 ///
 /// # Returns
 ///
-/// A [`LaggedUrfResult`] where the keys are reach identifiers (`i32`), and the values are `LaggedUrfByDate`
-/// maps with `NaiveDate` keys and `f64` values representing the lagged URF for each date.
+/// A [`LaggedUrfResult`] where the keys are reach identifiers (`i32`), and the values are
+/// [`LaggedUrfByDate`] series starting in the first month of usage with the lagged URF for each month.
 pub fn urf_lagging(usage: &HashMap<NaiveDate, f64>, urf: Vec<UrfValue>) -> LaggedUrfResult {
     let reaches = urf.iter().map(|u| u.reach).unique().collect::<Vec<_>>();
-    let usage_dates: Vec<&NaiveDate> = usage.keys().into_iter().sorted().collect();
+    let usage = MonthlySeries::from(usage);
 
     let mut lagged_result = HashMap::new();
     for reach in reaches {
-        let mut reach_lagged = HashMap::new();
         let reach_urf = urf
             .iter()
             .filter(|u| u.reach == reach)
             .sorted_by_key(|u| u.month)
             .map(|u| u.urf_val)
             .collect::<Vec<f64>>();
+        if usage.is_empty() {
+            lagged_result.insert(reach, MonthlySeries::default());
+            continue;
+        }
 
-        for usage_date in &usage_dates {
-            let month_usage = usage.get(*usage_date).unwrap_or(&0.0);
+        let mut reach_lagged =
+            MonthlySeries::zeros(usage.start_date(), usage.len() + reach_urf.len() - 1);
+        let lagged_values = reach_lagged.values_mut();
+        for (usage_month, month_usage) in usage.values().iter().enumerate() {
             for (i, urf) in reach_urf.iter().enumerate() {
-                let urf_date = usage_date.add(Months::new(i as u32));
-                let urf_dep = month_usage * urf;
-                *reach_lagged.entry(urf_date).or_insert(0.0) += urf_dep;
+                lagged_values[usage_month + i] += month_usage * urf;
             }
         }
 
@@ -86,25 +89,38 @@ pub fn urf_lagging(usage: &HashMap<NaiveDate, f64>, urf: Vec<UrfValue>) -> Lagge
 /// * A `NaiveDate` representing the start of a month.
 /// * A `f64` value representing the streamflow depletion for that month (in acre-ft/month).
 ///
-/// The vector only includes months when the depletion is greater than 0.001 acre-ft/month.
-/// The calculation stops if a negative depletion value is encountered, indicating complete aquifer depletion.
+/// Every month from the first to the last month of any reach is included, as with
+/// [`ReportingOptions::dense`].
 pub fn combined_urf_results(values: LaggedUrfResult) -> Vec<(NaiveDate, f64)> {
-    // Aggregate f64 values by NaiveDate
-    let mut date_sums: HashMap<NaiveDate, f64> = HashMap::new();
-
-    // Iterate over all inner HashMaps
-    for inner_map in values.values() {
-        for (date, value) in inner_map {
-            // Sum values for each date
-            *date_sums.entry(*date).or_insert(0.0) += value;
+    combined_urf_results_with_reporting(values, &ReportingOptions::dense())
+}
+
+/// Creates a combined result of depletion from the lagged URF results, reported as set by `reporting`.
+///
+/// This applies the same threshold, zero filling and negative value handling as the model
+/// calculations, see [`crate::calculate_streamflow_depletion_with_options`].
+///
+/// # Parameters
+///
+/// - `values`: A [`LaggedUrfResult`] containing the lagged URF results for each reach.
+/// - `reporting`: The threshold, zero filling and negative value handling.
+///
+/// # Returns
+///
+/// A vector of tuples of the start of each reported month and the depletion summed over the reaches.
+pub fn combined_urf_results_with_reporting(
+    values: LaggedUrfResult,
+    reporting: &ReportingOptions,
+) -> Vec<(NaiveDate, f64)> {
+    // Sum values for each month over all reach series
+    let mut combined = MonthlySeries::default();
+    for reach_lagged in values.values() {
+        for (date, value) in reach_lagged.iter() {
+            combined.add(date, value);
         }
     }
 
-    // Convert to Vec and sort by date
-    let mut result: Vec<(NaiveDate, f64)> = date_sums.into_iter().collect();
-    result.sort_by(|a, b| a.0.cmp(&b.0));
-
-    result
+    create_results_vector(combined.iter(), reporting)
 }
 
 #[cfg(test)]
@@ -144,8 +160,8 @@ mod tests {
         let mut reach2 = HashMap::new();
         reach2.insert(NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(), 10.0);
         reach2.insert(NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(), 10.0);
-        expected_lagged.insert(1, reach1);
-        expected_lagged.insert(2, reach2);
+        expected_lagged.insert(1, MonthlySeries::from(&reach1));
+        expected_lagged.insert(2, MonthlySeries::from(&reach2));
 
         let result = urf_lagging(&usage, urf);
         assert_eq!(result, expected_lagged);
@@ -199,10 +215,28 @@ mod tests {
         reach2.insert(NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(), 20.0);
         reach2.insert(NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(), 30.0);
         reach2.insert(NaiveDate::from_ymd_opt(2024, 9, 1).unwrap(), 10.0);
-        expected_lagged.insert(1, reach1);
-        expected_lagged.insert(2, reach2);
+        expected_lagged.insert(1, MonthlySeries::from(&reach1));
+        expected_lagged.insert(2, MonthlySeries::from(&reach2));
 
         let result = urf_lagging(&usage, urf);
         assert_eq!(result, expected_lagged);
+
+        let combined = combined_urf_results(result.clone());
+        assert_eq!(combined.len(), 6);
+        assert_eq!(
+            combined[0],
+            (NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(), 60.0)
+        );
+        let reported = combined_urf_results_with_reporting(
+            result,
+            &ReportingOptions::default().with_threshold(15.0),
+        );
+        assert_eq!(
+            reported
+                .iter()
+                .map(|(_, value)| *value)
+                .collect::<Vec<f64>>(),
+            vec![60.0, 30.0, 70.0, 90.0, 40.0]
+        );
     }
 }
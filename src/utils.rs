@@ -9,7 +9,7 @@ use chrono::{Datelike, NaiveDate};
 ///
 /// * `date`: The starting `NaiveDate` to which months will be added.
 /// * `months`: The number of months to add. Can be positive (to add months) or
-///             negative (to subtract months).
+///   negative (to subtract months).
 ///
 /// # Returns
 ///
@@ -26,6 +26,87 @@ pub fn add_months(date: NaiveDate, months: i32) -> Option<NaiveDate> {
     NaiveDate::from_ymd_opt(year, month as u32, date.day())
 }
 
+/// Computes the scaled complementary error function, `erfcx(x) = exp(x²) * erfc(x)`.
+///
+/// Solutions with a streambed resistance term multiply a growing exponential by a vanishing
+/// `erfc`, which overflows or loses all precision when evaluated directly. This evaluates the
+/// product accurately to near machine precision for all `x`.
+///
+/// # Parameters
+///
+/// * `x`: The argument of the function.
+///
+/// # Returns
+///
+/// Returns `exp(x²) * erfc(x)`.
+pub(crate) fn erfcx(x: f64) -> f64 {
+    if x < 0.0 {
+        // erfc(-x) = 2 - erfc(x)
+        return 2.0 * (x * x).exp() - erfcx(-x);
+    }
+
+    if x < 2.0 {
+        // Series for erf(x) * exp(x²) with only positive terms
+        let mut term = x;
+        let mut sum = x;
+        let mut n = 0.0;
+        while term > sum * 1e-17 {
+            n += 1.0;
+            term *= 2.0 * x * x / (2.0 * n + 1.0);
+            sum += term;
+        }
+        return (x * x).exp() - 2.0 / std::f64::consts::PI.sqrt() * sum;
+    }
+
+    // Continued fraction for large arguments, evaluated from the tail
+    let mut fraction = x;
+    for k in (1..=80).rev() {
+        fraction = x + (k as f64 / 2.0) / fraction;
+    }
+    1.0 / (std::f64::consts::PI.sqrt() * fraction)
+}
+
+/// Evaluates the time integral of the Glover response `erfc(a / √τ)`, scaled by the time.
+///
+/// The integral of `erfc(a / √τ)` from 0 to `t` is `t` times this function evaluated at
+/// `u = a / √t`, which is `4 i²erfc(u) = (1 + 2u²) erfc(u) - 2u exp(-u²) / √π`. The terms are
+/// evaluated with [`erfcx`] because they nearly cancel for large `u`.
+///
+/// # Parameters
+///
+/// * `u`: The argument of the complementary error function at the end of the interval.
+///
+/// # Returns
+///
+/// Returns `(1 + 2u²) erfc(u) - 2u exp(-u²) / √π`, which ranges from 1 at `u = 0` towards 0.
+pub(crate) fn erfc_integral(u: f64) -> f64 {
+    (-u * u).exp() * ((1.0 + 2.0 * u * u) * erfcx(u) - 2.0 * u / std::f64::consts::PI.sqrt())
+}
+
+/// Integrates a function over an interval with the composite Simpson's rule.
+///
+/// # Parameters
+///
+/// * `f`: The function to integrate.
+/// * `a`: The lower limit of integration.
+/// * `b`: The upper limit of integration.
+/// * `intervals`: The number of subintervals, which is rounded up to an even number.
+///
+/// # Returns
+///
+/// Returns the approximate integral of `f` from `a` to `b`.
+pub(crate) fn simpson<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, intervals: usize) -> f64 {
+    let n = intervals.max(2).next_multiple_of(2);
+    let h = (b - a) / n as f64;
+    let interior: f64 = (1..n)
+        .map(|i| {
+            let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+            weight * f(a + i as f64 * h)
+        })
+        .sum();
+    (f(a) + interior + f(b)) * h / 3.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,4 +132,22 @@ mod tests {
         let result = add_months(start_date, 1);
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_erfcx() {
+        // Reference values of exp(x²) * erfc(x)
+        let expected = [
+            (0.0, 1.0),
+            (0.5, 0.615_690_344_192_925_9),
+            (1.0, 0.427_583_576_155_807),
+            (2.0, 0.255_395_676_310_505_7),
+            (3.0, 0.179_001_151_181_389_93),
+            (10.0, 0.056_140_992_743_822_594),
+        ];
+        for (x, value) in expected {
+            assert!((erfcx(x) - value).abs() < 1e-13);
+        }
+        assert!((erfcx(-1.0) - 2.0 * 1f64.exp() + erfcx(1.0)).abs() < 1e-13);
+        assert!(erfcx(1.0e6).is_finite());
+    }
 }
@@ -0,0 +1,350 @@
+use crate::engine::{DepletionByStream, calculate_streamflow_depletion_by_stream};
+use crate::error::DepletionError;
+use crate::glover_infinite::stream_reach_depletion_fraction;
+use crate::model::DepletionModel;
+use crate::params::AquiferParams;
+use crate::units::{Length, LengthUnit};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// Calculates streamflow depletion for a well inside a wedge formed by two stream branches.
+///
+/// This function computes the monthly streamflow depletion of each branch for a well near a
+/// river bend or the confluence of two streams.
+///
+/// # Parameters
+///
+/// * `pumping_volumes_monthly`: A HashMap containing monthly pumping volumes in acre-ft/month, keyed by date.
+/// * `wedge`: The wedge geometry, well location and aquifer properties.
+/// * `first_stream`: The identifier used for the first branch in the results.
+/// * `second_stream`: The identifier used for the second branch in the results.
+/// * `days_per_month`: The average number of days per month used in calculations.
+/// * `total_months`: The total number of months to calculate depletion for.
+///
+/// # Returns
+///
+/// A [`DepletionByStream`] with the monthly depletion of each branch in acre-ft/month, keyed by
+/// the stream identifiers.
+///
+/// # Errors
+///
+/// Returns a [`DepletionError`] if the two stream identifiers are the same or the pumping,
+/// parameters or horizon are invalid; see
+/// [`calculate_streamflow_depletion`](crate::calculate_streamflow_depletion).
+pub fn calculate_streamflow_depletion_wedge(
+    pumping_volumes_monthly: &HashMap<NaiveDate, f64>, // Monthly pumping volumes in acre-ft / month
+    wedge: &Wedge,
+    first_stream: i32,
+    second_stream: i32,
+    days_per_month: f64,
+    total_months: usize,
+) -> Result<DepletionByStream, DepletionError> {
+    let first = wedge.branch(WedgeBranch::First);
+    let second = wedge.branch(WedgeBranch::Second);
+    calculate_streamflow_depletion_by_stream(
+        &[(first_stream, &first), (second_stream, &second)],
+        pumping_volumes_monthly,
+        days_per_month,
+        total_months,
+    )
+}
+
+/// One of the two stream branches that form a [`Wedge`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WedgeBranch {
+    /// The branch along the x-axis.
+    First,
+    /// The branch at the wedge angle from the x-axis.
+    Second,
+}
+
+/// A wedge-shaped aquifer between two fully penetrating stream branches that meet at an angle of `π / n`.
+///
+/// The well is located with coordinates measured from the point where the branches meet, with
+/// the x-axis along the first branch and the second branch at the wedge angle, turning towards
+/// positive y. Both branches act as constant-head boundaries, which are represented by `2n`
+/// image wells.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wedge {
+    /// The well coordinate along the first branch (in length units, typically feet).
+    pub well_x: f64,
+    /// The well coordinate perpendicular to the first branch (in length units, typically feet).
+    pub well_y: f64,
+    /// The divisor `n` of the wedge angle `π / n`, so 2 gives a 90° wedge.
+    pub angle_divisor: u32,
+    /// Storativity or specific yield of the aquifer (dimensionless).
+    pub specific_yield: f64,
+    /// Transmissivity of the aquifer (in length²/time units, typically ft²/day).
+    pub transmissivity: f64,
+}
+
+impl Wedge {
+    pub fn new(
+        well_x: f64,
+        well_y: f64,
+        angle_divisor: u32,
+        specific_yield: f64,
+        transmissivity: f64,
+    ) -> Self {
+        Wedge {
+            well_x,
+            well_y,
+            angle_divisor,
+            specific_yield,
+            transmissivity,
+        }
+    }
+
+    /// Creates the wedge from validated aquifer parameters.
+    ///
+    /// # Parameters
+    ///
+    /// * `well_x`: The well coordinate along the first branch.
+    /// * `well_y`: The well coordinate perpendicular to the first branch.
+    /// * `angle_divisor`: The divisor `n` of the wedge angle `π / n`.
+    /// * `aquifer`: The validated storage and transmissivity of the aquifer.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DepletionError`] if the angle divisor is zero or the well is outside the wedge.
+    pub fn from_aquifer(
+        well_x: Length,
+        well_y: Length,
+        angle_divisor: u32,
+        aquifer: &AquiferParams,
+    ) -> Result<Self, DepletionError> {
+        let wedge = Wedge::new(
+            well_x.value(LengthUnit::Feet),
+            well_y.value(LengthUnit::Feet),
+            angle_divisor,
+            aquifer.specific_yield(),
+            aquifer.transmissivity(),
+        );
+        wedge.validate()?;
+        Ok(wedge)
+    }
+
+    /// Creates a 90° wedge, such as the confluence of two perpendicular streams.
+    ///
+    /// # Parameters
+    ///
+    /// * `distance_to_first_stream`: The perpendicular distance from the well to the first stream.
+    /// * `distance_to_second_stream`: The perpendicular distance from the well to the second stream.
+    /// * `specific_yield`: Storativity of the aquifer (dimensionless).
+    /// * `transmissivity`: Transmissivity of the aquifer (in length²/time units, typically ft²/day).
+    pub fn right_angle(
+        distance_to_first_stream: f64,
+        distance_to_second_stream: f64,
+        specific_yield: f64,
+        transmissivity: f64,
+    ) -> Self {
+        Wedge::new(
+            distance_to_second_stream,
+            distance_to_first_stream,
+            2,
+            specific_yield,
+            transmissivity,
+        )
+    }
+
+    /// Checks that the wedge is physically possible and the well is inside it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DepletionError::NonPhysicalParameter`] for the first parameter out of range.
+    pub fn validate(&self) -> Result<(), DepletionError> {
+        if self.angle_divisor == 0 {
+            return Err(DepletionError::NonPhysicalParameter {
+                name: "angle divisor",
+                value: 0.0,
+            });
+        }
+        AquiferParams::new(self.specific_yield, self.transmissivity)?;
+        let well_angle = self.well_y.atan2(self.well_x);
+        if !(well_angle > 0.0 && well_angle < self.angle()) {
+            return Err(DepletionError::NonPhysicalParameter {
+                name: "well angle",
+                value: well_angle,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the wedge angle in radians.
+    pub fn angle(&self) -> f64 {
+        PI / self.angle_divisor as f64
+    }
+
+    /// Returns the depletion model of one of the branches.
+    pub fn branch(&self, branch: WedgeBranch) -> WedgeStream {
+        WedgeStream {
+            wedge: *self,
+            branch,
+        }
+    }
+
+    /// Returns the real well and its image wells as `(x, y, sign)`.
+    ///
+    /// The images are the rotations of the well by multiples of twice the wedge angle, and their
+    /// reflections across the first branch with the opposite sign.
+    fn image_wells(&self) -> Vec<(f64, f64, f64)> {
+        let radius = self.well_x.hypot(self.well_y);
+        let well_angle = self.well_y.atan2(self.well_x);
+        (0..self.angle_divisor)
+            .flat_map(|k| {
+                let rotation = 2.0 * k as f64 * self.angle();
+                let (y, x) = (well_angle + rotation).sin_cos();
+                let (image_y, image_x) = (rotation - well_angle).sin_cos();
+                [
+                    (radius * x, radius * y, 1.0),
+                    (radius * image_x, radius * image_y, -1.0),
+                ]
+            })
+            .collect()
+    }
+}
+
+/// The depletion of one branch of a [`Wedge`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WedgeStream {
+    wedge: Wedge,
+    branch: WedgeBranch,
+}
+
+impl DepletionModel for WedgeStream {
+    fn name(&self) -> &'static str {
+        match self.branch {
+            WedgeBranch::First => "Wedge aquifer, first branch",
+            WedgeBranch::Second => "Wedge aquifer, second branch",
+        }
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("well x", self.wedge.well_x),
+            ("well y", self.wedge.well_y),
+            ("angle divisor", self.wedge.angle_divisor as f64),
+            ("specific yield", self.wedge.specific_yield),
+            ("transmissivity", self.wedge.transmissivity),
+        ]
+    }
+
+    /// Returns the steady-state share of the pumping captured from this branch.
+    ///
+    /// Mapping the wedge onto a half-plane with `z^n` places the well at `n` times its angle from
+    /// the first branch, and a well in a half-plane draws from each side of the boundary in
+    /// proportion to the angle it subtends.
+    fn ultimate_fraction(&self) -> f64 {
+        let share = self.wedge.well_y.atan2(self.wedge.well_x) / self.wedge.angle();
+        match self.branch {
+            WedgeBranch::First => 1.0 - share,
+            WedgeBranch::Second => share,
+        }
+    }
+
+    fn validate(&self) -> Result<(), DepletionError> {
+        self.wedge.validate()
+    }
+
+    /// Calculates the fraction of pumping captured from this branch.
+    ///
+    /// The image wells come in pairs mirrored across each branch line with opposite signs, so the
+    /// flow into the branch is the sum of the flow from each pair on the wedge side of the line into
+    /// the half of the line that forms the branch.
+    ///
+    /// # Parameters
+    ///
+    /// * `time`: Time since pumping began (in time units, typically days).
+    fn depletion_fraction(&self, time: f64) -> f64 {
+        let angle = self.wedge.angle();
+        self.wedge
+            .image_wells()
+            .into_iter()
+            .map(|(x, y, sign)| match self.branch {
+                WedgeBranch::First => (x, y, sign),
+                WedgeBranch::Second => {
+                    // rotate the second branch onto the x-axis and flip the wedge above it
+                    let (sin, cos) = angle.sin_cos();
+                    (x * cos + y * sin, x * sin - y * cos, sign)
+                }
+            })
+            .filter(|(_, y, _)| *y > 0.0)
+            .map(|(x, y, sign)| {
+                sign * stream_reach_depletion_fraction(
+                    y,
+                    -x,
+                    f64::INFINITY,
+                    self.wedge.specific_yield,
+                    self.wedge.transmissivity,
+                    time,
+                )
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glover_infinite::GloverInfinite;
+
+    #[test]
+    fn test_straight_stream_splits_glover() {
+        // A wedge of 180° is a straight stream, split into two branches at the origin
+        let s: f64 = 0.2;
+        let t: f64 = 261_800.0 / 7.481;
+        let wedge = Wedge::new(1500.0, 4000.0, 1, s, t);
+        let glover = GloverInfinite::new(4000.0, s, t);
+
+        for time in [30.0, 365.0, 3650.0] {
+            let first = wedge.branch(WedgeBranch::First).depletion_fraction(time);
+            let second = wedge.branch(WedgeBranch::Second).depletion_fraction(time);
+            assert!(first > second);
+            assert!((first + second - glover.depletion_fraction(time)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_right_angle_confluence() {
+        let s: f64 = 0.2;
+        let t: f64 = 261_800.0 / 7.481;
+
+        // Equal distances to both streams split the depletion equally
+        let wedge = Wedge::right_angle(3000.0, 3000.0, s, t);
+        let first = wedge.branch(WedgeBranch::First);
+        let second = wedge.branch(WedgeBranch::Second);
+        for time in [30.0, 365.0, 3650.0] {
+            assert!(
+                (first.depletion_fraction(time) - second.depletion_fraction(time)).abs() < 1e-9
+            );
+        }
+        let time = 1.0e7;
+        assert!(
+            (first.depletion_fraction(time) + second.depletion_fraction(time) - 1.0).abs() < 1e-3
+        );
+
+        // A well close to one stream and far from the other behaves like the Glover solution early on
+        let wedge = Wedge::right_angle(1000.0, 50_000.0, s, t);
+        let glover = GloverInfinite::new(1000.0, s, t);
+        let first = wedge.branch(WedgeBranch::First);
+        assert!((first.depletion_fraction(30.0) - glover.depletion_fraction(30.0)).abs() < 1e-6);
+
+        let mut pumping_volumes = HashMap::new();
+        pumping_volumes.insert(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), 100.0);
+        let value =
+            calculate_streamflow_depletion_wedge(&pumping_volumes, &wedge, 1, 2, 30.42, 120)
+                .unwrap();
+        let first_total: f64 = value[&1].iter().map(|(_, v)| v).sum();
+        let second_total: f64 = value[&2].iter().map(|(_, v)| v).sum();
+        assert!(first_total > second_total);
+        assert!(first_total + second_total < 100.0 + 1e-6);
+
+        let aquifer = AquiferParams::new(0.2, 35_000.0).unwrap();
+        let wedge =
+            Wedge::from_aquifer(Length::meters(609.6), Length::meters(304.8), 2, &aquifer).unwrap();
+        assert!((wedge.well_x - 2000.0).abs() < 1e-9);
+        assert!((wedge.well_y - 1000.0).abs() < 1e-9);
+        assert!(Wedge::from_aquifer(Length::feet(-10.0), Length::feet(1.0), 2, &aquifer).is_err());
+    }
+}